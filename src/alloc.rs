@@ -0,0 +1,84 @@
+// Allocation counts for a phase of a day, gathered by a counting global allocator when built with
+// the alloc-stats feature. Counts are kept per thread so days running alongside each other on the
+// pool don't show up in each other's phases
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub count: u64,
+    pub bytes: u64,
+    pub peak: u64,
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    use super::AllocStats;
+
+    thread_local! {
+        static COUNT: Cell<u64> = const { Cell::new(0) };
+        static BYTES: Cell<u64> = const { Cell::new(0) };
+        // Memory freed here may have been allocated on another thread, so live can go negative
+        static LIVE: Cell<i64> = const { Cell::new(0) };
+        static PEAK: Cell<i64> = const { Cell::new(0) };
+    }
+
+    // Thread locals are unavailable while a thread is torn down, allocations then go uncounted
+    fn record(allocated: usize, freed: usize) {
+        let _ = LIVE.try_with(|live| {
+            live.set(live.get() + allocated as i64 - freed as i64);
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+        });
+        if allocated > 0 {
+            let _ = COUNT.try_with(|x| x.set(x.get() + 1));
+            let _ = BYTES.try_with(|x| x.set(x.get() + allocated as u64));
+        }
+    }
+
+    struct Counting;
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            record(layout.size(), 0);
+            System.alloc(layout)
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            record(layout.size(), 0);
+            System.alloc_zeroed(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            record(0, layout.size());
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            record(new_size, layout.size());
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+        let (count, bytes, live) = (COUNT.get(), BYTES.get(), LIVE.get());
+        PEAK.set(live);
+        let res = func();
+        let stats = AllocStats {
+            count: COUNT.get() - count,
+            bytes: BYTES.get() - bytes,
+            peak: (PEAK.get() - live).max(0) as u64,
+        };
+        (res, Some(stats))
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+pub use counting::measure;
+
+#[cfg(not(feature = "alloc-stats"))]
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    (func(), None)
+}
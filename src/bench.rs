@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use advent_of_code_2024::{days, Error};
+
+use crate::output::print_table;
+use crate::{read_input, Args};
+
+pub struct Options {
+    pub warmup: usize,
+    pub iterations: usize,
+    pub threshold: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Phase {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            "part1" => Ok(Phase::Part1),
+            "part2" => Ok(Phase::Part2),
+            _ => Err(format!("unknown phase '{s}'")),
+        }
+    }
+}
+
+// Summary statistics over the timed runs of a phase
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    std_dev: Duration,
+}
+
+impl Stats {
+    fn new(samples: &mut [Duration]) -> Self {
+        samples.sort();
+        let len = samples.len();
+        let median = if len.is_multiple_of(2) {
+            (samples[len / 2 - 1] + samples[len / 2]) / 2
+        } else {
+            samples[len / 2]
+        };
+
+        let mean = samples.iter().map(|x| x.as_secs_f64()).sum::<f64>() / len as f64;
+        let variance = samples
+            .iter()
+            .map(|x| (x.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / len as f64;
+
+        Stats {
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+struct Measurement {
+    day: u32,
+    phase: Phase,
+    stats: Stats,
+}
+
+// Runs the function untimed for the warmup, then times each of the remaining iterations,
+// stopping at the first error
+fn sample<T>(
+    options: &Options,
+    mut func: impl FnMut() -> Result<T, Error>,
+) -> Result<Stats, Error> {
+    for _ in 0..options.warmup {
+        black_box(func()?);
+    }
+
+    let mut samples = Vec::new();
+    for _ in 0..options.iterations.max(1) {
+        let start = Instant::now();
+        black_box(func()?);
+        samples.push(start.elapsed());
+    }
+
+    Ok(Stats::new(&mut samples))
+}
+
+fn bench_day(
+    day: u32,
+    input: &str,
+    args: &Args,
+    options: &Options,
+) -> Result<Vec<Measurement>, Error> {
+    let solver = days::parse_day(day, input)?;
+
+    let mut res = vec![Measurement {
+        day,
+        phase: Phase::Parse,
+        stats: sample(options, || days::parse_day(day, input))?,
+    }];
+    for (part, phase) in [(1, Phase::Part1), (2, Phase::Part2)] {
+        if args.part.numbers().contains(&part) {
+            res.push(Measurement {
+                day,
+                phase,
+                stats: sample(options, || days::solve(day, solver.as_ref(), part))?,
+            });
+        }
+    }
+
+    Ok(res)
+}
+
+// Baseline files hold one line per phase: day, phase, then min, median, mean and standard
+// deviation in nanoseconds
+fn save(path: &str, measurements: &[Measurement]) -> Result<(), Error> {
+    let contents = measurements
+        .iter()
+        .map(|x| {
+            format!(
+                "{} {} {} {} {} {}\n",
+                x.day,
+                x.phase,
+                x.stats.min.as_nanos(),
+                x.stats.median.as_nanos(),
+                x.stats.mean.as_nanos(),
+                x.stats.std_dev.as_nanos()
+            )
+        })
+        .collect::<String>();
+
+    fs::write(path, contents).map_err(|why| Error::io(Path::new(path), why))
+}
+
+fn load(path: &str) -> Result<HashMap<(u32, Phase), Stats>, Error> {
+    fn parse_line(line: &str) -> Option<((u32, Phase), Stats)> {
+        let mut split = line.split_whitespace();
+        let day = str::parse(split.next()?).ok()?;
+        let phase = str::parse(split.next()?).ok()?;
+        let mut next = || Some(Duration::from_nanos(str::parse(split.next()?).ok()?));
+        let stats = Stats {
+            min: next()?,
+            median: next()?,
+            mean: next()?,
+            std_dev: next()?,
+        };
+        Some(((day, phase), stats))
+    }
+
+    let contents = fs::read_to_string(path).map_err(|why| Error::io(Path::new(path), why))?;
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(y, line)| {
+            parse_line(line).ok_or_else(|| Error::InvalidFile {
+                path: path.into(),
+                line: Some(y + 1),
+                message: format!("invalid baseline line '{line}'"),
+            })
+        })
+        .collect()
+}
+
+// Describes the change in median against the baseline, flagging slowdowns over the threshold
+fn compare(stats: &Stats, baseline: &Stats, options: &Options) -> String {
+    let old = baseline.median.as_secs_f64();
+    let new = stats.median.as_secs_f64();
+    let change = if old > 0.0 {
+        (new - old) / old * 100.0
+    } else {
+        0.0
+    };
+
+    if change > options.threshold {
+        format!("{change:+.1}% REGRESSION")
+    } else {
+        format!("{change:+.1}%")
+    }
+}
+
+pub fn run(
+    selected: RangeInclusive<u32>,
+    args: &Args,
+    options: &Options,
+    save_path: Option<&str>,
+    baseline_path: Option<&str>,
+) -> Result<(), Error> {
+    let baseline = baseline_path.map(load).transpose()?;
+
+    let mut measurements = Vec::new();
+    for day in selected.filter(|day| days::is_implemented(*day)) {
+        match read_input(day, args) {
+            Ok(input) => measurements.extend(bench_day(day, &input, args, options)?),
+            Err(why) => eprintln!("Skipping day {day}: {why}"),
+        }
+    }
+
+    let rows = measurements
+        .iter()
+        .map(|x| {
+            let mut row = vec![
+                x.day.to_string(),
+                x.phase.to_string(),
+                format!("{:.2?}", x.stats.min),
+                format!("{:.2?}", x.stats.median),
+                format!("{:.2?}", x.stats.mean),
+                format!("{:.2?}", x.stats.std_dev),
+            ];
+            if let Some(baseline) = &baseline {
+                row.push(match baseline.get(&(x.day, x.phase)) {
+                    Some(old) => compare(&x.stats, old, options),
+                    None => "no baseline".to_string(),
+                });
+            }
+            row
+        })
+        .collect::<Vec<_>>();
+
+    let mut header = vec!["Day", "Phase", "Min", "Median", "Mean", "Std dev"];
+    if baseline.is_some() {
+        header.push("Change");
+    }
+    print_table(&header, &rows);
+
+    if let Some(path) = save_path {
+        save(path, &measurements)?;
+    }
+    Ok(())
+}
@@ -1,57 +1,83 @@
 use std::collections::HashMap;
 
+use super::{Answer, Solver};
+use crate::error::{ParseError, SolveError};
 use crate::shared::*;
 
-pub fn run(input: String) {
-    let mut left = Vec::new();
-    let mut right = Vec::new();
-
-    // Parse input data
-    let parsed = match parse::<u32>(&input, " ") {
-        Err(why) => panic!("Unable to parse input: {}", why),
-        Ok(parsed) => parsed,
-    };
-
-    // Format into left and right arrays
-    parsed
-        .iter()
-        .map(|line| {
-            if let [l, r] = line[..] {
-                left.push(l);
-                right.push(r);
-            }
-        })
-        .for_each(drop);
-
-    left.sort();
-    right.sort();
-
-    // Total up distances
-    let mut total: i64 = 0;
-    std::iter::zip(left.clone(), right.clone())
-        .map(|(l, r)| {
-            let diff: i64 = (r as i64) - (l as i64);
-            let diff: i64 = diff.abs();
-            total += diff
-        })
-        .for_each(drop);
-
-    println!("Total distance is {total}");
-
-    let mut lfreq = HashMap::new();
-
-    left.iter()
-        .map(|x| lfreq.insert(*x, freq(*x, &right)))
-        .for_each(drop);
-
-    let sim: usize = left
-        .iter()
-        .map(|x| *x as usize * *lfreq.get(x).expect("Failed to find frequency for {x}"))
-        .sum();
-
-    println!("Similarity: {sim}");
+pub struct Lists {
+    left: Vec<u32>,
+    right: Vec<u32>,
+}
+
+impl Solver for Lists {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+
+        // Parse input data
+        let parsed = parse::<u32>(input, " ", "a location ID")?;
+
+        // Format into left and right arrays
+        parsed
+            .iter()
+            .map(|line| {
+                if let [l, r] = line[..] {
+                    left.push(l);
+                    right.push(r);
+                }
+            })
+            .for_each(drop);
+
+        left.sort();
+        right.sort();
+
+        Ok(Lists { left, right })
+    }
+
+    fn part1(&self) -> Result<Answer, SolveError> {
+        // Total up distances
+        let mut total: i64 = 0;
+        std::iter::zip(self.left.clone(), self.right.clone())
+            .map(|(l, r)| {
+                let diff: i64 = (r as i64) - (l as i64);
+                let diff: i64 = diff.abs();
+                total += diff
+            })
+            .for_each(drop);
+
+        Ok(total.into())
+    }
+
+    fn part2(&self) -> Result<Answer, SolveError> {
+        let mut lfreq = HashMap::new();
+
+        self.left
+            .iter()
+            .map(|x| lfreq.insert(*x, freq(*x, &self.right)))
+            .for_each(drop);
+
+        let sim: usize = self
+            .left
+            .iter()
+            .map(|x| *x as usize * *lfreq.get(x).expect("Failed to find frequency for {x}"))
+            .sum();
+
+        Ok(sim.into())
+    }
 }
 
 fn freq(target: u32, list: &[u32]) -> usize {
     list.iter().filter(|&&x| x == target).count()
 }
+
+examples! {
+    Lists,
+    example: "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+" => (Some("11"), Some("31")),
+}
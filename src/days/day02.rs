@@ -1,20 +1,29 @@
+use super::{Answer, Solver};
+use crate::error::{ParseError, SolveError};
 use crate::shared::*;
 
-pub fn run(input: String) {
-    // Parse input data
-    let parsed = match parse::<u32>(&input, " ") {
-        Err(why) => panic!("Unable to parse input: {}", why),
-        Ok(parsed) => parsed,
-    };
+pub struct Reports(Vec<Vec<u32>>);
+
+impl Solver for Reports {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        // Parse input data
+        Ok(Reports(parse::<u32>(input, " ", "a level")?))
+    }
+
+    fn part1(&self) -> Result<Answer, SolveError> {
+        let safe = self.0.iter().map(|x| is_safe(x)).filter(|x| *x).count();
+        Ok(safe.into())
+    }
 
-    let safe = parsed.iter().map(|x| is_safe(x)).filter(|x| *x).count();
-    println!("Safe plans: {safe}");
-    let safe_dampened = parsed
-        .iter()
-        .map(|x| is_safe_dampened(x))
-        .filter(|x| *x)
-        .count();
-    println!("Safe plans with dampening: {safe_dampened}");
+    fn part2(&self) -> Result<Answer, SolveError> {
+        let safe_dampened = self
+            .0
+            .iter()
+            .map(|x| is_safe_dampened(x))
+            .filter(|x| *x)
+            .count();
+        Ok(safe_dampened.into())
+    }
 }
 
 fn is_safe(plan: &[u32]) -> bool {
@@ -61,3 +70,15 @@ fn is_safe_dampened(plan: &[u32]) -> bool {
 
     false
 }
+
+examples! {
+    Reports,
+    example: "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+" => (Some("2"), Some("4")),
+}
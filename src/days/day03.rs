@@ -1,11 +1,23 @@
-pub fn run(input: String) {
-    println!("Multiply sum: {}", sum_all_multiplies(&input));
+use super::{Answer, Solver};
+use crate::error::{ParseError, SolveError};
 
-    // Part 2
-    let do_split = input.split("do()");
-    let trimmed = do_split.map(|x| x.split("don't()").next().unwrap());
-    let sum: u32 = trimmed.map(sum_all_multiplies).sum();
-    println!("Multiply sum with control flow: {sum}");
+pub struct Program(String);
+
+impl Solver for Program {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Program(input.to_string()))
+    }
+
+    fn part1(&self) -> Result<Answer, SolveError> {
+        Ok(sum_all_multiplies(&self.0).into())
+    }
+
+    fn part2(&self) -> Result<Answer, SolveError> {
+        let do_split = self.0.split("do()");
+        let trimmed = do_split.map(|x| x.split("don't()").next().unwrap());
+        let sum: u32 = trimmed.map(sum_all_multiplies).sum();
+        Ok(sum.into())
+    }
 }
 
 fn sum_all_multiplies(input: &str) -> u32 {
@@ -23,3 +35,13 @@ fn parse_mul(val: &str) -> Option<u32> {
     let r = str::parse::<u32>(split.next()?).ok()?;
     Some(l * r)
 }
+
+examples! {
+    Program,
+    example_part1: "\
+xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))
+" => (Some("161"), None),
+    example_part2: "\
+xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))
+" => (None, Some("48")),
+}
@@ -1,5 +1,11 @@
 use std::fmt;
 
+use super::{Answer, Solver};
+use crate::error::{ParseError, SolveError};
+use crate::shared::{Coord, Direction, FromChar, Grid};
+
+pub type Puzzle = WordSearch;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Char {
     X,
@@ -19,6 +25,21 @@ impl Char {
     }
 }
 
+// Letters other than those of XMAS can't be part of a word, so are kept as None
+impl FromChar for Option<Char> {
+    const EXPECTED: &'static str = "any character";
+
+    fn from_char(c: char) -> Option<Self> {
+        Some(match c {
+            'X' => Some(Char::X),
+            'M' => Some(Char::M),
+            'A' => Some(Char::A),
+            'S' => Some(Char::S),
+            _ => None,
+        })
+    }
+}
+
 impl fmt::Display for Char {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let output = match self {
@@ -41,111 +62,25 @@ impl fmt::Display for Char {
 //    }
 //}
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    UpLeft,
-    Up,
-    UpRight,
-    Right,
-    DownRight,
-    Down,
-    DownLeft,
-    Left,
-}
-
-impl Direction {
-    fn iter() -> impl Iterator<Item = Self> {
-        [
-            Self::UpLeft,
-            Self::Up,
-            Self::UpRight,
-            Self::Right,
-            Self::DownRight,
-            Self::Down,
-            Self::DownLeft,
-            Self::Left,
-        ]
-        .iter()
-        .copied()
-    }
-}
-
-#[derive(Debug, Copy, Clone)]
-struct Coord<const N: usize> {
-    x: usize,
-    y: usize,
-}
-
-impl<const N: usize> Coord<N> {
-    fn new(x: i32, y: i32) -> Option<Coord<N>> {
-        if x >= 0 && y >= 0 && x < N as i32 && y < N as i32 {
-            let x = x as usize;
-            let y = y as usize;
-            Some(Coord { x, y })
-        } else {
-            None
-        }
-    }
-
-    fn adj(&self, dir: &Direction) -> Option<Coord<N>> {
-        let (x, y) = (self.x as i32, self.y as i32);
-        let (new_x, new_y) = match dir {
-            Direction::UpLeft => (x - 1, y - 1),
-            Direction::Up => (x, y - 1),
-            Direction::UpRight => (x + 1, y - 1),
-            Direction::Right => (x + 1, y),
-            Direction::DownRight => (x + 1, y + 1),
-            Direction::Down => (x, y + 1),
-            Direction::DownLeft => (x - 1, y + 1),
-            Direction::Left => (x - 1, y),
-        };
-        Coord::new(new_x, new_y)
-    }
-}
-
-struct WordSearch<const N: usize> {
-    data: [[Option<Char>; N]; N], //data: Vec<Vec<Option<Char>>>,
+pub struct WordSearch {
+    data: Grid<Option<Char>>,
 }
 
-impl<const N: usize> WordSearch<N> {
-    fn new(input: &str) -> Option<WordSearch<N>> {
-        fn parse_char(c: char) -> Option<Char> {
-            match c {
-                'X' => Some(Char::X),
-                'M' => Some(Char::M),
-                'A' => Some(Char::A),
-                'S' => Some(Char::S),
-                _ => None,
-            }
-        }
-
-        fn parse_line<const M: usize>(line: &str) -> Option<[Option<Char>; M]> {
-            line.chars()
-                .map(parse_char)
-                .collect::<Vec<_>>()
-                .try_into()
-                .ok()
-        }
-
-        let lines = input.split("\n");
-        let data = lines
-            .map(parse_line::<N>)
-            .collect::<Option<Vec<_>>>()?
-            .try_into()
-            .ok()?;
-
-        Some(WordSearch { data })
+impl WordSearch {
+    fn new(input: &str) -> Result<WordSearch, ParseError> {
+        let data = Grid::from_chars(input)?;
+        Ok(WordSearch { data })
     }
 
-    fn get(&self, coord: Coord<N>) -> &Option<Char> {
-        &self.data[coord.y][coord.x]
+    fn get(&self, coord: Coord) -> &Option<Char> {
+        self.data.get(&coord)
     }
 
-    fn get_word<const LEN: usize>(&self, coord: &Coord<N>, dir: Direction) -> Option<[char; LEN]> {
+    fn get_word<const LEN: usize>(&self, coord: &Coord, dir: Direction) -> Option<[char; LEN]> {
         // Generate coords list
         let mut coords = vec![*coord];
         for _ in 1..LEN {
-            coords.push(coords.last()?.adj(&dir)?)
+            coords.push(self.data.next(*coords.last()?, dir)?)
         }
         let coords: [_; LEN] = coords.try_into().ok()?;
 
@@ -162,15 +97,15 @@ impl<const N: usize> WordSearch<N> {
         chars
     }
 
-    fn is_cross_mas(&self, coord: &Coord<N>) -> bool {
+    fn is_cross_mas(&self, coord: &Coord) -> bool {
         const TARGET: [char; 3] = ['M', 'A', 'S'];
         const TARGET_REV: [char; TARGET.len()] = ['S', 'A', 'M'];
         const LEN: usize = TARGET.len();
 
-        let Some(up_left) = coord.adj(&Direction::UpLeft) else {
+        let Some(up_left) = self.data.next(*coord, Direction::UpLeft) else {
             return false;
         };
-        let Some(up_right) = coord.adj(&Direction::UpRight) else {
+        let Some(up_right) = self.data.next(*coord, Direction::UpRight) else {
             return false;
         };
 
@@ -185,49 +120,58 @@ impl<const N: usize> WordSearch<N> {
     }
 }
 
-impl<const N: usize> fmt::Display for WordSearch<N> {
+impl fmt::Display for WordSearch {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let output = self
-            .data
-            .iter()
-            .map(|line| {
-                line.iter()
-                    .map(|char| match char {
-                        Some(c) => c.to_string(),
-                        None => ".".to_string(),
-                    })
-                    .collect::<Vec<_>>()
-                    .join("")
-            })
-            .collect::<Vec<_>>()
-            .join("\n");
-        write!(f, "{output}")
+        let output = self.data.map(|char| match char {
+            Some(c) => c.to_char(),
+            None => '.',
+        });
+        write!(f, "{}", output.to_string().trim_end())
     }
 }
 
-pub fn run(input: String) {
-    const DIM: usize = 140;
-    //const DIM: usize = 10;
-
-    let ws = WordSearch::<DIM>::new(input.trim())
-        .expect(&format!("Failed to parse {DIM}x{DIM} wordsearch")[..]);
+impl Solver for WordSearch {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        WordSearch::new(input)
+    }
 
-    let mut xmas_count = 0;
-    let mut cross_mas_count = 0;
-    for y in 0..DIM {
-        for x in 0..DIM {
-            let coord = Coord::new(x as i32, y as i32).unwrap();
-            for dir in Direction::iter() {
-                if ws.get_word::<4>(&coord, dir) == Some(['X', 'M', 'A', 'S']) {
+    fn part1(&self) -> Result<Answer, SolveError> {
+        let mut xmas_count: usize = 0;
+        for coord in self.data.coords() {
+            for dir in Direction::all() {
+                if self.get_word::<4>(&coord, dir) == Some(['X', 'M', 'A', 'S']) {
                     xmas_count += 1
                 }
             }
-            if ws.is_cross_mas(&coord) {
+        }
+
+        Ok(xmas_count.into())
+    }
+
+    fn part2(&self) -> Result<Answer, SolveError> {
+        let mut cross_mas_count: usize = 0;
+        for coord in self.data.coords() {
+            if self.is_cross_mas(&coord) {
                 cross_mas_count += 1;
             }
         }
+
+        Ok(cross_mas_count.into())
     }
+}
 
-    println!("XMAS count: {xmas_count}");
-    println!("X-MAS count: {cross_mas_count}");
+examples! {
+    WordSearch,
+    example: "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+" => (Some("18"), Some("9")),
 }
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 use std::fmt;
-use std::io;
-use std::io::Write;
 
+use super::{Answer, Solver};
+use crate::error::{ParseError, SolveError};
+use crate::debug;
+use crate::shared::parser::*;
 use crate::shared::*;
 
 #[derive(Debug)]
@@ -12,16 +14,16 @@ struct Rule {
 }
 
 impl Rule {
-    fn new(line: &str) -> Option<Self> {
-        let (before, after) = line.split_once("|")?;
-        let before = str::parse::<u32>(before).ok()?;
-        let after = str::parse::<u32>(after).ok()?;
-
-        Some(Rule { before, after })
+    // "47|53", the page before the bar must be printed before the page after it
+    fn parser<'a>() -> impl Parser<'a, Self> {
+        let page = || named(int(), "a page number");
+        map(pair(terminated(page(), literal("|")), page()), |(before, after)| {
+            Rule { before, after }
+        })
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Update(Vec<u32>);
 
 impl Update {
@@ -29,7 +31,7 @@ impl Update {
         for (i, elem) in self.0.iter().enumerate() {
             let after = &self.0[i..];
             let Some(required_preceeding) = rules.get(elem) else {
-                return true;
+                continue;
             };
             for x in after {
                 if required_preceeding.contains(x) {
@@ -56,19 +58,17 @@ impl Update {
 }
 
 #[derive(Debug)]
-struct PrintJob {
+pub struct PrintJob {
     rules: HashMap<u32, Vec<u32>>,
     updates: Vec<Update>,
 }
 
 impl PrintJob {
-    fn new(input: String) -> Option<Self> {
-        let (rules, updates) = input.trim().split_once("\n\n")?;
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let update = map(separated(named(int(), "a page number"), literal(",")), Update);
+        let grammar = pair(terminated(lines(Rule::parser()), blank_line()), lines(update));
+        let (rules, updates) = parse_all(grammar, input)?;
 
-        let rules = rules
-            .split("\n")
-            .map(Rule::new)
-            .collect::<Option<Vec<_>>>()?;
         let mut rules_table: HashMap<u32, Vec<u32>> = HashMap::new();
         for rule in rules {
             let entry = rules_table.get_mut(&rule.after);
@@ -80,13 +80,7 @@ impl PrintJob {
             }
         }
 
-        let updates = parse::<u32>(updates, ",")
-            .ok()?
-            .into_iter()
-            .map(Update)
-            .collect();
-
-        Some(PrintJob {
+        Ok(PrintJob {
             rules: rules_table,
             updates,
         })
@@ -157,10 +151,10 @@ impl<T: std::hash::Hash + std::cmp::Eq + Copy + std::fmt::Debug> Graph<T> {
     }
 
     fn longest_path(&self) -> Option<Vec<&Node<T>>> {
-        //println!("Starting looking for longets path in:\n{self:?}");
+        debug!("Starting looking for longest path in:\n{self:?}");
         let mut curr = vec![self.top_node()];
         self.path(&mut curr)?;
-        //println!("Longest path found: {curr:?}");
+        debug!("Longest path found: {curr:?}");
         Some(curr)
     }
 
@@ -216,26 +210,73 @@ impl<T: std::hash::Hash + std::cmp::Eq + Copy + std::fmt::Debug> Graph<T> {
     }
 }
 
-pub fn run(input: String) {
-    let mut job = PrintJob::new(input).expect("Unable to parse print job");
-    let len = job.updates.len();
+impl Solver for PrintJob {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let job = PrintJob::new(input)?;
+        debug!("Job contains {} updates", job.updates.len());
+        Ok(job)
+    }
 
-    println!("Job contains {len} updates");
+    fn part1(&self) -> Result<Answer, SolveError> {
+        let valid_acc: u32 = self
+            .updates
+            .iter()
+            .filter(|update| update.check(&self.rules))
+            .map(|update| update.middle())
+            .sum();
 
-    let mut valid_acc = 0;
-    let mut reorder_acc = 0;
-    for (i, update) in &mut job.updates.iter_mut().enumerate() {
-        print!("\r{}%", (100 * i) / (len));
-        io::stdout().flush().expect("Failed to flush stdout");
-        let valid = update.check(&job.rules);
-        if valid {
-            valid_acc += update.middle()
-        } else {
-            update.reorder(&job.rules);
-            reorder_acc += update.middle()
+        Ok(valid_acc.into())
+    }
+
+    fn part2(&self) -> Result<Answer, SolveError> {
+        let mut progress = Progress::new("Reordering", self.updates.len());
+
+        let mut reorder_acc = 0;
+        for (i, update) in self.updates.iter().enumerate() {
+            progress.update(i);
+            if !update.check(&self.rules) {
+                let mut update = update.clone();
+                update.reorder(&self.rules).ok_or_else(|| {
+                    SolveError(format!("unable to reorder update {:?}", update.0))
+                })?;
+                reorder_acc += update.middle()
+            }
         }
+
+        Ok(reorder_acc.into())
     }
+}
+
+examples! {
+    PrintJob,
+    example: "\
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
 
-    println!("Valid pattern total: {valid_acc:?}");
-    println!("Reordered pattern total: {reorder_acc:?}");
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+" => (Some("143"), Some("123")),
 }
@@ -1,6 +1,13 @@
 use core::panic;
 use std::fmt;
 
+use super::{Answer, Solver};
+use crate::error::{ParseError, SolveError};
+use crate::shared::{Coord, Direction, FromChar, Grid, Progress};
+use crate::{debug, trace};
+
+pub type Puzzle = Map;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Facing {
     Up,
@@ -28,10 +35,10 @@ impl Facing {
         }
     }
 
-    fn new(val: Self) -> [bool; 4] {
+    fn history(val: Self) -> [bool; 4] {
         let mut arr = [false; 4];
         arr[val.index()] = true;
-        return arr;
+        arr
     }
 
     fn add(curr: &mut [bool; 4], val: Self) {
@@ -41,6 +48,15 @@ impl Facing {
     fn get(curr: &mut [bool; 4], val: Self) -> bool {
         curr[val.index()]
     }
+
+    fn direction(&self) -> Direction {
+        match self {
+            Facing::Up => Direction::Up,
+            Facing::Right => Direction::Right,
+            Facing::Down => Direction::Down,
+            Facing::Left => Direction::Left,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -51,18 +67,6 @@ enum Cell {
     Guard(Facing, [bool; 4]),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Coord {
-    x: usize,
-    y: usize,
-}
-
-impl fmt::Display for Coord {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "({},{})", self.x, self.y)
-    }
-}
-
 impl fmt::Display for Cell {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let c = match self {
@@ -78,8 +82,10 @@ impl fmt::Display for Cell {
     }
 }
 
-impl Cell {
-    fn new(c: char) -> Option<Self> {
+impl FromChar for Cell {
+    const EXPECTED: &'static str = "one of . # ^ > v <";
+
+    fn from_char(c: char) -> Option<Self> {
         match c {
             '.' => Some(Self::Empty),
             '#' => Some(Self::Obstruction),
@@ -90,96 +96,66 @@ impl Cell {
             _ => None,
         }
     }
+}
 
+impl Cell {
     fn new_guard(dir: Facing) -> Self {
-        Cell::Guard(dir, Facing::new(dir))
-    }
-
-    fn is_guard(&self) -> bool {
-        match self {
-            Self::Guard(_, _) => true,
-            _ => false,
-        }
+        Cell::Guard(dir, Facing::history(dir))
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Map<const N: usize> {
-    data: [[Cell; N]; N],
+#[derive(Debug, Clone)]
+pub struct Map {
+    data: Grid<Cell>,
     guard: Option<Coord>,
 }
 
-impl<const N: usize> fmt::Display for Map<N> {
+impl fmt::Display for Map {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let grid = self
-            .data
-            .map(|line| line.map(|char| char.to_string()).join(""))
-            .join("\n");
+        let grid = &self.data;
         let guard = match self.guard {
-            Some(guard) => format!("Guard at ({},{})", guard.x, guard.y),
+            Some(guard) => format!("Guard at {guard}"),
             None => "Guard not present".to_string(),
         };
 
-        write!(f, "{grid}\n{guard}")
+        write!(f, "{grid}{guard}")
     }
 }
 
-impl<const N: usize> Map<N> {
-    fn new(input: &str) -> Option<Self> {
-        let data: [[Cell; N]; N] = input
-            .trim()
-            .split("\n")
-            .map(|line| {
-                line.chars()
-                    .map(Cell::new)
-                    .collect::<Option<Vec<_>>>()?
-                    .try_into()
-                    .ok()
-            })
-            .collect::<Option<Vec<[Cell; N]>>>()?
-            .try_into()
-            .ok()?;
-
-        let (y, Some(x)) = data
-            .map(|row| -> Option<usize> {
-                row.iter()
-                    .enumerate()
-                    .find(|(_, cell)| cell.is_guard())
-                    .map(|(i, _)| i)
-            })
-            .into_iter()
-            .enumerate()
-            .find(|(_, x)| x.is_some())?
-        else {
-            return None;
+impl Map {
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let (data, guards) = Grid::<Cell>::from_chars_marked(input, &['^', '>', 'v', '<'])?;
+        let guard = match guards[..] {
+            [guard] => guard.coord,
+            [] => return Err(ParseError::new(1, 1, "missing guard").expected("one of ^ > v <")),
+            [_, extra, ..] => {
+                return Err(ParseError::new(extra.line, extra.column, "second guard")
+                    .token(extra.mark)
+                    .expected("a single guard"))
+            }
         };
 
-        Some(Map {
+        Ok(Map {
             data,
-            guard: Some(Coord { x, y }),
+            guard: Some(guard),
         })
     }
 
     fn get(&self, coord: Coord) -> &Cell {
-        &self.data[coord.y][coord.x]
-    }
-
-    fn get_mut(&mut self, coord: Coord) -> &mut Cell {
-        &mut self.data[coord.y][coord.x]
+        self.data.get(&coord)
     }
 
     fn set(&mut self, coord: Coord, state: Cell) {
-        self.data[coord.y][coord.x] = state
+        self.data.set(state, coord)
     }
 
     // Tests if the given state is a looping state
     fn is_loop(&mut self) -> bool {
-        //println!("Testing loop");
+        trace!("Testing loop");
         while self.guard.is_some() {
-            //println!("{}", self.guard.unwrap());
+            trace!("Guard at {}", self.guard.unwrap());
             let repeat = self.next();
             if repeat {
-                println!("{self}");
                 return true;
             }
         }
@@ -196,23 +172,9 @@ impl<const N: usize> Map<N> {
         };
         let facing = *facing;
 
-        // Calculate possible next coords
-        let (x, y) = (guard_coord.x as i32, guard_coord.y as i32);
-        let (x, y) = match facing {
-            Facing::Up => (x, y - 1),
-            Facing::Left => (x - 1, y),
-            Facing::Down => (x, y + 1),
-            Facing::Right => (x + 1, y),
-        };
-
         // Determine next guard state
-        if x >= 0 && y >= 0 && x < N as i32 && y < N as i32 {
+        if let Some(next_coord) = self.data.next(guard_coord, facing.direction()) {
             // Coord in bounds, guard has next valid coord
-            let next_coord = Coord {
-                x: x as usize,
-                y: y as usize,
-            };
-
             match self.get(next_coord) {
                 Cell::Obstruction => {
                     // Guard rotates on the same spot
@@ -240,15 +202,15 @@ impl<const N: usize> Map<N> {
         };
         let current_facing = *current_facing;
         let Some((next_loc, next_facing)) = self.next_coord() else {
-            self.set(current_loc, Cell::Visited(Facing::new(current_facing)));
+            self.set(current_loc, Cell::Visited(Facing::history(current_facing)));
             self.guard = None;
             return false;
         };
 
         // Check if cell is repeating
-        let target = self.get_mut(next_loc);
+        let mut target = *self.get(next_loc);
         let repeat;
-        if let Cell::Visited(dirs) = target {
+        if let Cell::Visited(dirs) = &mut target {
             repeat = Facing::get(dirs, next_facing)
         } else {
             repeat = false
@@ -262,8 +224,8 @@ impl<const N: usize> Map<N> {
         self.set(current_loc, current_new_state);
 
         // Generate new guard cell
-        let next = self.get_mut(next_loc);
-        let next_new = match next {
+        let mut next = *self.get(next_loc);
+        let next_new = match &mut next {
             Cell::Empty => Cell::new_guard(next_facing),
             Cell::Visited(dirs) => {
                 Facing::add(dirs, next_facing);
@@ -277,48 +239,84 @@ impl<const N: usize> Map<N> {
         repeat
     }
 
+    // Walks the guard until they leave the map, returning the final state and the steps taken
+    fn patrol(&self) -> (Self, usize) {
+        let mut map = self.clone();
+        let mut steps = 0;
+        while map.guard.is_some() {
+            map.next();
+            steps += 1;
+        }
+        (map, steps)
+    }
+
     fn place_obstacle(&mut self) -> Option<()> {
         let (next_coord, _) = self.next_coord()?;
         if self.guard? != next_coord {
             self.set(next_coord, Cell::Obstruction);
             Some(())
         } else {
-            println!("Not placing obstruction at {next_coord}, guard currently there");
+            debug!("Not placing obstruction at {next_coord}, guard currently there");
             None
         }
     }
 }
 
-pub fn run(input: String) {
-    let mut map: Map<130> = Map::new(&input).expect("Could not parse map");
-    //let mut map: Map<10> = Map::new(&input).expect("Could not parse map");
+impl Solver for Map {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Map::new(input)
+    }
+
+    fn part1(&self) -> Result<Answer, SolveError> {
+        let (map, _) = self.patrol();
 
-    let mut locs = Vec::new();
-    while let Some(loc) = map.guard {
-        locs.push(loc);
-        map.next();
+        let count = map
+            .data
+            .iter()
+            .filter(|x| matches!(x, Cell::Visited(_)))
+            .count();
+        Ok(count.into())
     }
 
-    let count = map
-        .data
-        .concat()
-        .iter()
-        .filter(|x| matches!(x, Cell::Visited(_)))
-        .count();
-    println!("Visited: {}", count);
-
-    let mut map: Map<130> = Map::new(&input).expect("Could not parse map");
-    //let mut map: Map<10> = Map::new(&input).expect("Could not parse map");
-    println!("{map}");
-    let mut loops = 0;
-    while map.guard.is_some() {
-        let mut test_map = map;
-        test_map.place_obstacle();
-        if test_map.is_loop() {
-            loops += 1;
-        };
-        map.next();
+    fn part2(&self) -> Result<Answer, SolveError> {
+        let mut progress = Progress::sized_by("Placing obstructions", || self.patrol().1);
+
+        let mut map = self.clone();
+        let mut loops: usize = 0;
+        let mut step = 0;
+        while map.guard.is_some() {
+            progress.update(step);
+            step += 1;
+            let mut test_map = map.clone();
+            test_map.place_obstacle();
+            if test_map.is_loop() {
+                loops += 1;
+            };
+            map.next();
+        }
+
+        Ok(loops.into())
     }
 
-    println!("Loops: {loops}");
+    fn render(&self) -> Option<String> {
+        // Show the route the guard patrols
+        let (map, _) = self.patrol();
+        Some(map.data.to_string())
+    }
+}
+
+examples! {
+    Map,
+    example: "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+" => (Some("41"), Some("6")),
 }
@@ -1,5 +1,9 @@
 use std::fmt;
 
+use super::{Answer, Explanation, Solver};
+use crate::error::{ParseError, SolveError};
+use crate::shared::parser::*;
+
 #[derive(Debug, Clone)]
 struct Equation {
     target: u64,
@@ -20,16 +24,13 @@ impl fmt::Display for Equation {
 }
 
 impl Equation {
-    fn new(input: &str) -> Option<Self> {
-        let mut split = input.split(": ");
-        let target = str::parse(split.next()?).ok()?;
-        let values = split
-            .next()?
-            .split(" ")
-            .map(str::parse)
-            .collect::<Result<Vec<_>, _>>()
-            .ok()?;
-        Some(Equation { target, values })
+    // "190: 10 19", the target then the values to combine
+    fn parser<'a>() -> impl Parser<'a, Self> {
+        let target = named(int(), "a target number");
+        let values = separated(named(int(), "a number"), literal(" "));
+        map(key_value(target, ": ", values), |(target, values)| {
+            Equation { target, values }
+        })
     }
 
     fn solve(self, ops: &[Operator]) -> Option<Solution> {
@@ -38,7 +39,7 @@ impl Equation {
                 return partial.check();
             }
 
-            for op in ops.into_iter() {
+            for op in ops.iter() {
                 partial.push(*op);
                 if let Some(sol) = helper(partial, ops) {
                     return Some(sol);
@@ -221,32 +222,74 @@ impl Solution {
     //}
 }
 
-pub fn run(input: String) {
-    let equations = input
-        .trim()
-        .split("\n")
-        .map(Equation::new)
-        .collect::<Option<Vec<_>>>()
-        .expect("Unable to parse input");
-
-    let solutions = equations
-        .clone()
-        .into_iter()
-        .map(|eq| eq.solve(&[Operator::Add, Operator::Mul]))
-        .filter(|x| x.is_some())
-        .collect::<Option<Vec<_>>>()
-        .unwrap();
-
-    let solutions_concat = equations
-        .into_iter()
-        .map(|eq| eq.solve(&[Operator::Add, Operator::Concat, Operator::Mul]))
-        .filter(|x| x.is_some())
-        .collect::<Option<Vec<_>>>()
-        .unwrap();
-
-    let target_total: u64 = solutions.iter().map(|x| x.target).sum();
-    let target_total_contcat: u64 = solutions_concat.iter().map(|x| x.target).sum();
-
-    println!("Total valid targets: {target_total}");
-    println!("Total valid targets with concat: {target_total_contcat}");
+pub struct Equations(Vec<Equation>);
+
+impl Equations {
+    fn ops(part: u8) -> &'static [Operator] {
+        match part {
+            1 => &[Operator::Add, Operator::Mul],
+            _ => &[Operator::Add, Operator::Concat, Operator::Mul],
+        }
+    }
+
+    // Solution to each equation, if it has one with the given operators
+    fn breakdown(&self, ops: &[Operator]) -> Vec<Option<Solution>> {
+        self.0.iter().map(|eq| eq.clone().solve(ops)).collect()
+    }
+
+    fn total(solutions: &[Option<Solution>]) -> u64 {
+        solutions.iter().flatten().map(|x| x.target).sum()
+    }
+}
+
+impl Solver for Equations {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let equations = parse_all(lines(Equation::parser()), input)?;
+        Ok(Equations(equations))
+    }
+
+    fn part1(&self) -> Result<Answer, SolveError> {
+        Ok(Self::total(&self.breakdown(Self::ops(1))).into())
+    }
+
+    fn part2(&self) -> Result<Answer, SolveError> {
+        Ok(Self::total(&self.breakdown(Self::ops(2))).into())
+    }
+
+    fn explain(&self, part: u8) -> Option<Result<(Answer, Explanation), SolveError>> {
+        let solutions = self.breakdown(Self::ops(part));
+        let rows = self
+            .0
+            .iter()
+            .zip(&solutions)
+            .map(|(eq, solution)| {
+                vec![
+                    eq.to_string(),
+                    solution
+                        .as_ref()
+                        .map_or("unsolvable".to_string(), |x| x.to_string()),
+                ]
+            })
+            .collect();
+        let explanation = Explanation {
+            header: vec!["Equation", "Solution"],
+            rows,
+        };
+        Some(Ok((Self::total(&solutions).into(), explanation)))
+    }
+}
+
+examples! {
+    Equations,
+    example: "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+" => (Some("3749"), Some("11387")),
 }
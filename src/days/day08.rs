@@ -1,18 +1,22 @@
+use super::{Answer, Solver};
+use crate::error::{ParseError, SolveError};
 use crate::shared::*;
 
-struct Map<const N: usize>(Grid<char, N>);
+pub type Puzzle = Map;
 
-impl<const N: usize> std::ops::Deref for Map<N> {
-    type Target = Grid<char, N>;
+pub struct Map(Grid<char>);
+
+impl std::ops::Deref for Map {
+    type Target = Grid<char>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl<const N: usize> Map<N> {
-    fn new(input: &str) -> Option<Self> {
-        Some(Map(Grid::<char, N>::new(input, "")?))
+impl Map {
+    fn new(input: &str) -> Result<Self, ParseError> {
+        Ok(Map(Grid::from_chars(input)?))
     }
 
     fn unique(&self) -> std::collections::HashSet<char> {
@@ -24,7 +28,7 @@ impl<const N: usize> Map<N> {
     }
 }
 
-fn pairwise<const N: usize>(values: Vec<Coord<N>>) -> impl Iterator<Item = (Coord<N>, Coord<N>)> {
+fn pairwise(values: Vec<Coord>) -> impl Iterator<Item = (Coord, Coord)> {
     values
         .clone()
         .into_iter()
@@ -34,10 +38,7 @@ fn pairwise<const N: usize>(values: Vec<Coord<N>>) -> impl Iterator<Item = (Coor
         .into_iter()
 }
 
-fn compute<const N: usize>(
-    map: &Map<N>,
-    pairfn: fn((Coord<N>, Coord<N>)) -> Vec<Coord<N>>,
-) -> Vec<Coord<N>> {
+fn compute(map: &Map, pairfn: fn(&Map, (Coord, Coord)) -> Vec<Coord>) -> Vec<Coord> {
     map.unique()
         .iter()
         .filter_map(|char| {
@@ -46,27 +47,25 @@ fn compute<const N: usize>(
             }
 
             let coords = map.find(*char);
-            Some(pairwise(coords).map(pairfn))
+            Some(pairwise(coords).map(|pair| pairfn(map, pair)))
         })
         .flatten()
         .flatten()
         .collect::<Vec<_>>()
 }
 
-fn pt1<const N: usize>((l, r): (Coord<N>, Coord<N>)) -> Vec<Coord<N>> {
+fn pt1(map: &Map, (l, r): (Coord, Coord)) -> Vec<Coord> {
     let (xdiff, ydiff) = r - l;
     let (lx, ly) = (l.x() as i64, l.y() as i64);
     let (rx, ry) = (r.x() as i64, r.y() as i64);
 
-    let new1 = (lx - xdiff, ly - ydiff);
-    let new2 = (rx + xdiff, ry + ydiff);
-    let new1: Option<Coord<N>> = new1.try_into().ok();
-    let new2: Option<Coord<N>> = new2.try_into().ok();
+    let new1 = map.coord(lx - xdiff, ly - ydiff);
+    let new2 = map.coord(rx + xdiff, ry + ydiff);
 
     vec![new1, new2].into_iter().flatten().collect::<Vec<_>>()
 }
 
-fn pt2<const N: usize>((l, r): (Coord<N>, Coord<N>)) -> Vec<Coord<N>> {
+fn pt2(map: &Map, (l, r): (Coord, Coord)) -> Vec<Coord> {
     let (xdiff, ydiff) = r - l;
     let (lx, ly) = (l.x() as i64, l.y() as i64);
     let (rx, ry) = (r.x() as i64, r.y() as i64);
@@ -74,13 +73,13 @@ fn pt2<const N: usize>((l, r): (Coord<N>, Coord<N>)) -> Vec<Coord<N>> {
     let mut res = Vec::new();
 
     let (mut x, mut y) = (lx, ly);
-    while let Ok(coord) = Coord::try_from((x, y)) {
+    while let Some(coord) = map.coord(x, y) {
         res.push(coord);
         (x, y) = (x - xdiff, y - ydiff);
     }
 
     let (mut x, mut y) = (rx, ry);
-    while let Ok(coord) = Coord::try_from((x, y)) {
+    while let Some(coord) = map.coord(x, y) {
         res.push(coord);
         (x, y) = (x + xdiff, y + ydiff);
     }
@@ -88,22 +87,54 @@ fn pt2<const N: usize>((l, r): (Coord<N>, Coord<N>)) -> Vec<Coord<N>> {
     res
 }
 
-pub fn run(input: String) {
-    const N: usize = 50;
-    let map: Map<N> = Map::new(&input).expect("Could not parse map");
-    let locations = compute(&map, pt1);
+fn antinodes(map: &Map, pairfn: fn(&Map, (Coord, Coord)) -> Vec<Coord>) -> usize {
+    let locations = compute(map, pairfn);
     let mut dedup = std::collections::HashSet::new();
     locations.iter().for_each(|x| {
         dedup.insert(x);
     });
+    dedup.len()
+}
 
-    println!("{}, {}", locations.len(), dedup.len());
+impl Solver for Map {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Map::new(input)
+    }
 
-    let locations = compute(&map, pt2);
-    let mut dedup = std::collections::HashSet::new();
-    locations.iter().for_each(|x| {
-        dedup.insert(x);
-    });
+    fn part1(&self) -> Result<Answer, SolveError> {
+        Ok(antinodes(self, pt1).into())
+    }
+
+    fn part2(&self) -> Result<Answer, SolveError> {
+        Ok(antinodes(self, pt2).into())
+    }
+
+    fn render(&self) -> Option<String> {
+        // Mark the antinodes that don't overlap an antenna
+        let mut grid = self.0.clone();
+        for coord in compute(self, pt1) {
+            if *grid.get(&coord) == '.' {
+                grid.set('#', coord);
+            }
+        }
+        Some(grid.to_string())
+    }
+}
 
-    println!("{}, {}", locations.len(), dedup.len());
+examples! {
+    Map,
+    example: "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+" => (Some("14"), Some("34")),
 }
@@ -1,6 +1,10 @@
 use core::panic;
 use std::collections::VecDeque;
 
+use super::{Answer, Solver};
+use crate::error::{ParseError, SolveError};
+use crate::shared::column;
+
 #[derive(Debug, Clone)]
 enum Block {
     File(u32, u8),
@@ -16,32 +20,56 @@ impl Block {
     }
 }
 
-#[derive(Debug)]
-struct DiskMap {
+#[derive(Debug, Clone)]
+pub struct DiskMap {
     data: Vec<Block>,
 }
 
 impl DiskMap {
-    fn new(input: &str) -> Self {
+    fn new(input: &str) -> Result<Self, ParseError> {
+        // The disk map is the first line that isn't blank, nothing may follow it
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let (y, line) = lines.next().unwrap_or((0, ""));
+        if let Some((y, extra)) = lines.next() {
+            let token = extra.split_whitespace().next().unwrap_or_default();
+            return Err(
+                ParseError::new(y + 1, column(extra, token), "unexpected input")
+                    .token(token)
+                    .expected("end of input"),
+            );
+        }
+
+        let digits = line.trim();
         let mut file = false;
         let mut id = 0;
-        DiskMap {
-            data: input
-                .trim()
-                .chars()
-                .map(|char| {
+        Ok(DiskMap {
+            data: digits
+                .char_indices()
+                .map(|(i, char)| {
                     file = !file;
-                    let len = str::parse(&char.to_string()).expect("Invalid block length: {char}");
+                    let x = column(line, &digits[i..]);
+                    if char.is_whitespace() {
+                        return Err(ParseError::new(y + 1, x, "unexpected whitespace")
+                            .expected("a digit"));
+                    }
+                    let len = str::parse(&char.to_string()).map_err(|_| {
+                        ParseError::new(y + 1, x, "invalid block length")
+                            .token(char)
+                            .expected("a digit")
+                    })?;
                     if file {
                         let res = Block::File(id, len);
                         id += 1;
-                        res
+                        Ok(res)
                     } else {
-                        Block::Empty(len)
+                        Ok(Block::Empty(len))
                     }
                 })
-                .collect(),
-        }
+                .collect::<Result<_, _>>()?,
+        })
     }
 
     fn raw_blocks(&self) -> Vec<Option<u32>> {
@@ -105,7 +133,7 @@ impl DiskMap {
             .filter(|x| matches!(x, Block::File(_, _)))
             .rev();
 
-        let mut src = &mut self.data;
+        let src = &mut self.data;
 
         for block in inserts {
             if let Block::File(insert_id, insert_size) = block {
@@ -120,7 +148,7 @@ impl DiskMap {
                 let block = std::mem::replace(src.get_mut(i).unwrap(), Block::Empty(insert_size));
 
                 // Insert element, worst case inserts at old position
-                insert(&mut src, block);
+                insert(src, block);
             }
         }
     }
@@ -137,16 +165,26 @@ fn checksum(blocks: &[Option<u32>]) -> u64 {
         .sum()
 }
 
-pub fn run(input: String) {
-    let mut map = DiskMap::new(&input);
+impl Solver for DiskMap {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        DiskMap::new(input)
+    }
+
+    fn part1(&self) -> Result<Answer, SolveError> {
+        let new = self.rearrange_blocks();
+        Ok(checksum(&new).into())
+    }
 
-    // Pt 1
-    let new = map.rearrange_blocks();
-    let sum = checksum(&new);
-    println!("{sum}");
+    fn part2(&self) -> Result<Answer, SolveError> {
+        let mut map = self.clone();
+        map.rearrange_files();
+        Ok(checksum(&map.raw_blocks()).into())
+    }
+}
 
-    // Pt 2
-    map.rearrange_files();
-    let sum = checksum(&map.raw_blocks());
-    println!("{sum}");
+examples! {
+    DiskMap,
+    example: "\
+2333133121414131402
+" => (Some("1928"), Some("2858")),
 }
@@ -1,43 +1,62 @@
+use std::fmt;
 use std::ops::Deref;
 
+use super::{Answer, Solver};
+use crate::error::{ParseError, SolveError};
 use crate::shared::*;
+use crate::trace;
 
-struct Map<const N: usize>(Grid<u8, N>);
+pub type Puzzle = Map;
 
-impl<const N: usize> Deref for Map<N> {
-    type Target = Grid<u8, N>;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Height(u8);
+
+impl FromChar for Height {
+    const EXPECTED: &'static str = "a height from 0 to 9";
+
+    fn from_char(c: char) -> Option<Self> {
+        c.to_digit(10).map(|x| Height(x as u8))
+    }
+}
+
+impl fmt::Display for Height {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub struct Map(Grid<Height>);
+
+impl Deref for Map {
+    type Target = Grid<Height>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl<const N: usize> Map<N> {
-    fn new(input: &str) -> Option<Self> {
-        Some(Map(Grid::new(input.trim(), "")?))
+impl Map {
+    fn new(input: &str) -> Result<Self, ParseError> {
+        Ok(Map(Grid::from_chars(input)?))
     }
 
-    fn paths(&self, start: Coord<N>) -> Vec<Vec<Coord<N>>> {
-        fn explore<const M: usize>(
-            map: &Map<M>,
-            curr: &mut Vec<Coord<M>>,
-            complete: &mut Vec<Vec<Coord<M>>>,
-        ) {
-            //println!("{curr:?}");
+    fn paths(&self, start: Coord) -> Vec<Vec<Coord>> {
+        fn explore(map: &Map, curr: &mut Vec<Coord>, complete: &mut Vec<Vec<Coord>>) {
+            trace!("Exploring {curr:?}");
             let curr_coord = *curr.last().expect("Current cells empty");
-            if *map.get(&curr_coord) == 9 {
+            if map.get(&curr_coord).0 == 9 {
                 // Route complete, add to finished vector
                 complete.push(curr.clone());
             } else {
                 for dir in Direction::orthogonal() {
-                    //println!("Trying {dir:?}");
-                    let next_coord = (curr_coord).next(dir);
+                    trace!("Trying {dir:?}");
+                    let next_coord = map.next(curr_coord, dir);
                     if let Some(next_coord) = next_coord {
-                        //println!(
-                        //    "Trying coord: {next_coord} with val {}",
-                        //    map.get(&next_coord)
-                        //);
-                        if map.get(&curr_coord) + 1 == *map.get(&next_coord) {
+                        trace!(
+                            "Trying coord: {next_coord} with val {}",
+                            map.get(&next_coord)
+                        );
+                        if map.get(&curr_coord).0 + 1 == map.get(&next_coord).0 {
                             // Is a valid next coord
                             curr.push(next_coord);
                             explore(map, curr, complete);
@@ -55,7 +74,7 @@ impl<const N: usize> Map<N> {
         res
     }
 
-    fn score(&self, start: Coord<N>) -> usize {
+    fn score(&self, start: Coord) -> usize {
         let mut set = std::collections::HashSet::new();
         self.paths(start)
             .iter()
@@ -67,21 +86,46 @@ impl<const N: usize> Map<N> {
     }
 
     fn total_score(&self) -> usize {
-        self.find(0).iter().map(|x| self.score(*x)).sum()
+        self.find(Height(0)).iter().map(|x| self.score(*x)).sum()
     }
 
-    fn rating(&self, start: Coord<N>) -> usize {
+    fn rating(&self, start: Coord) -> usize {
         self.paths(start).len()
     }
 
     fn total_rating(&self) -> usize {
-        self.find(0).iter().map(|x| self.rating(*x)).sum()
+        self.find(Height(0)).iter().map(|x| self.rating(*x)).sum()
+    }
+}
+
+impl Solver for Map {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Map::new(input)
+    }
+
+    fn part1(&self) -> Result<Answer, SolveError> {
+        Ok(self.total_score().into())
+    }
+
+    fn part2(&self) -> Result<Answer, SolveError> {
+        Ok(self.total_rating().into())
+    }
+
+    fn render(&self) -> Option<String> {
+        Some(self.0.to_string())
     }
 }
 
-pub fn run(input: String) {
-    const N: usize = 57;
-    let map = Map::<N>::new(&input).expect("Unable to parse map");
-    println!("Total score: {}", map.total_score());
-    println!("Total rating: {}", map.total_rating());
+examples! {
+    Map,
+    example: "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+" => (Some("36"), Some("81")),
 }
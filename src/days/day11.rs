@@ -1,5 +1,6 @@
-use crate::shared::Cache;
-use std::io::{stdout, Write};
+use super::{Answer, Solver};
+use crate::error::{ParseError, SolveError};
+use crate::shared::{parse, Cache, Progress};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct Stone(u64);
@@ -9,7 +10,7 @@ impl Stone {
         let str = self.0.to_string();
         let len = str.len();
 
-        if len % 2 == 0 {
+        if len.is_multiple_of(2) {
             let (l, r) = str.split_at(len / 2);
             let (l, r) = (str::parse(l).ok()?, str::parse(r).ok()?);
             Some((Stone(l), Stone(r)))
@@ -59,16 +60,16 @@ impl Stone {
 }
 
 #[derive(Debug, Clone)]
-struct Stones(Vec<Stone>);
+pub struct Stones(Vec<Stone>);
 
 impl Stones {
-    fn new(input: &str) -> Option<Self> {
-        let stones = input
-            .trim()
-            .split(" ")
-            .map(|x| Some(Stone(str::parse(x).ok()?)))
-            .collect::<Option<Vec<_>>>()?;
-        Some(Stones(stones))
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let stones = parse::<u64>(input, " ", "a stone number")?
+            .into_iter()
+            .flatten()
+            .map(Stone)
+            .collect();
+        Ok(Stones(stones))
     }
 
     #[allow(dead_code)]
@@ -90,13 +91,12 @@ impl Stones {
             .collect::<Vec<_>>();
 
         // Compute 25 -> 75 step, caching where possible
-        let count25 = stones25.len();
-        let x = stones25
+        let mut progress = Progress::new("Blinking", stones25.len());
+        stones25
             .iter()
             .enumerate()
             .map(|(i, stone25)| {
-                print!("\r{}% - {:?}", i * 100 / count25, stone25);
-                stdout().flush().expect("Failed to flush stdout");
+                progress.update_with(i, format_args!("- {stone25:?}"));
                 cache_end.get_or_set(*stone25, || {
                     // Value wasn't in instant cache, need to compute
                     let stones50 = stone25.skip_unordered_cache(25, &mut cache25);
@@ -110,10 +110,7 @@ impl Stones {
                     acc
                 })
             })
-            .sum();
-        println!();
-
-        x
+            .sum()
     }
 }
 
@@ -127,15 +124,28 @@ impl std::cmp::PartialEq for Stones {
     }
 }
 
-pub fn run(input: String) {
-    let stones = Stones::new(&input).expect("Unable to parse input");
-    let pt1 = stones
-        .0
-        .iter()
-        .flat_map(|stone| stone.skip_unordered(25))
-        .count();
-    println!("After 25 iterations, {pt1} stones present.");
-
-    let pt2 = stones.skip75();
-    println!("After 75 iterations, {pt2} stones present.");
+impl Solver for Stones {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Stones::new(input)
+    }
+
+    fn part1(&self) -> Result<Answer, SolveError> {
+        Ok(self
+            .0
+            .iter()
+            .flat_map(|stone| stone.skip_unordered(25))
+            .count()
+            .into())
+    }
+
+    fn part2(&self) -> Result<Answer, SolveError> {
+        Ok(self.skip75().into())
+    }
+}
+
+examples! {
+    Stones,
+    example: "\
+125 17
+" => (Some("55312"), None),
 }
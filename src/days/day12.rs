@@ -1,26 +1,61 @@
+use super::{Answer, Explanation, Solver};
+use crate::error::{ParseError, SolveError};
 use crate::shared::*;
 use core::panic;
 use std::fmt;
 
-struct Garden<const N: usize>(Grid<char, N>);
+pub type Puzzle = Garden;
 
-impl<const N: usize> std::ops::Deref for Garden<N> {
-    type Target = Grid<char, N>;
+pub struct Garden(Grid<char>);
+
+impl std::ops::Deref for Garden {
+    type Target = Grid<char>;
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl<const N: usize> Garden<N> {
-    fn new(input: &str) -> Option<Self> {
-        Some(Garden(Grid::new(input.trim(), "")?))
+// Fencing needed for a region, counted as its perimeter for part 1 and its sides for part 2
+struct Price {
+    plant: char,
+    area: usize,
+    fences: usize,
+}
+
+impl Price {
+    fn price(&self) -> usize {
+        self.area * self.fences
+    }
+}
+
+impl Garden {
+    fn breakdown(&self, part: u8) -> Vec<Price> {
+        self.regions()
+            .iter()
+            .map(|region| Price {
+                plant: *self.get(&region.0[0]),
+                area: region.area(),
+                fences: match part {
+                    1 => region.perimiter(self),
+                    _ => region.sides(self),
+                },
+            })
+            .collect()
     }
 
-    fn connected(&self, start: Coord<N>) -> Region<N> {
-        fn explore<const M: usize>(garden: &Garden<M>, curr: &mut Vec<Coord<M>>) {
+    fn total(prices: &[Price]) -> usize {
+        prices.iter().map(Price::price).sum()
+    }
+
+    fn new(input: &str) -> Result<Self, ParseError> {
+        Ok(Garden(Grid::from_chars(input)?))
+    }
+
+    fn connected(&self, start: Coord) -> Region {
+        fn explore(garden: &Garden, curr: &mut Vec<Coord>) {
             let top = *curr.last().expect("Unable to explore empty curr vec");
             for dir in Direction::orthogonal() {
-                if let Some(next) = top.next(dir) {
+                if let Some(next) = garden.next(top, dir) {
                     if garden.get(&top) == garden.get(&next) && !curr.contains(&next) {
                         curr.push(next);
                         explore(garden, curr);
@@ -34,8 +69,8 @@ impl<const N: usize> Garden<N> {
         Region(coords)
     }
 
-    fn regions(&self) -> Vec<Region<N>> {
-        let mut grid = Grid::<_, N>::fill(false);
+    fn regions(&self) -> Vec<Region> {
+        let mut grid = Grid::fill(self.width(), self.height(), false);
         let mut regions = Vec::new();
 
         loop {
@@ -52,10 +87,10 @@ impl<const N: usize> Garden<N> {
     }
 }
 
-struct Region<const N: usize>(Vec<Coord<N>>);
+struct Region(Vec<Coord>);
 
-impl<const N: usize> Region<N> {
-    fn perimiter(&self, garden: &Garden<N>) -> usize {
+impl Region {
+    fn perimiter(&self, garden: &Garden) -> usize {
         let region = &self.0;
         region
             .iter()
@@ -66,7 +101,7 @@ impl<const N: usize> Region<N> {
                         .expect("Cannot find perimiter of empty region"),
                 );
                 Direction::orthogonal()
-                    .filter(|dir| match coord.next(*dir) {
+                    .filter(|dir| match garden.next(*coord, *dir) {
                         Some(coord) => *garden.get(&coord) != *target_char,
                         None => true,
                     })
@@ -79,7 +114,7 @@ impl<const N: usize> Region<N> {
         self.0.len()
     }
 
-    fn sides(&self, garden: &Garden<N>) -> usize {
+    fn sides(&self, garden: &Garden) -> usize {
         // Calculate the next step from an edge
         enum NextStep {
             Continue,
@@ -88,20 +123,16 @@ impl<const N: usize> Region<N> {
         }
 
         // Requires a coord on the edge, and a direction of that edge
-        fn next_step<const M: usize>(
-            garden: &Garden<M>,
-            inside: &Coord<M>,
-            edge_dir: &Direction,
-        ) -> NextStep {
+        fn next_step(garden: &Garden, inside: &Coord, edge_dir: &Direction) -> NextStep {
             let region_char = *garden.get(inside);
-            let next_side_coord = inside.next(side_direction(*edge_dir));
-            if let Some(outside) = inside.next(*edge_dir) {
+            let next_side_coord = garden.next(*inside, side_direction(*edge_dir));
+            if let Some(outside) = garden.next(*inside, *edge_dir) {
                 // Not outside edge
                 if let Some(next_side_coord) = next_side_coord {
                     // Random mid square, check values
                     let next_side = *garden.get(&next_side_coord);
                     let next_side_outside =
-                        *garden.get(&outside.next(side_direction(*edge_dir)).unwrap());
+                        *garden.get(&garden.next(outside, side_direction(*edge_dir)).unwrap());
                     match (next_side == region_char, next_side_outside == region_char) {
                         (true, false) => NextStep::Continue,
                         (true, true) => NextStep::AntiClockwise,
@@ -139,7 +170,7 @@ impl<const N: usize> Region<N> {
             .0
             .iter()
             .flat_map(|coord| {
-                Direction::orthogonal().filter_map(|dir| match coord.next(dir) {
+                Direction::orthogonal().filter_map(|dir| match garden.next(*coord, dir) {
                     Some(adj) => {
                         if *garden.get(&adj) == region_char {
                             None
@@ -167,7 +198,7 @@ impl<const N: usize> Region<N> {
                 match next_step(garden, &curr, &outside_dir) {
                     NextStep::Continue => {
                         let side_dir = side_direction(outside_dir);
-                        curr = curr.next(side_dir).unwrap();
+                        curr = garden.next(curr, side_dir).unwrap();
                     }
                     NextStep::Clockwise => {
                         outside_dir = side_direction(outside_dir);
@@ -176,8 +207,8 @@ impl<const N: usize> Region<N> {
                     NextStep::AntiClockwise => {
                         // Eww
                         let side_dir = side_direction(outside_dir);
-                        curr = curr.next(side_dir).unwrap();
-                        curr = curr.next(outside_dir).unwrap();
+                        curr = garden.next(curr, side_dir).unwrap();
+                        curr = garden.next(curr, outside_dir).unwrap();
                         outside_dir = side_direction(outside_dir);
                         outside_dir = side_direction(outside_dir);
                         outside_dir = side_direction(outside_dir);
@@ -193,34 +224,70 @@ impl<const N: usize> Region<N> {
 
         count
     }
-
-    fn cost(&self, garden: &Garden<N>) -> usize {
-        self.perimiter(garden) * self.area()
-    }
-
-    fn discount_cost(&self, garden: &Garden<N>) -> usize {
-        self.sides(garden) * self.area()
-    }
 }
 
-impl<const N: usize> fmt::Display for Region<N> {
+impl fmt::Display for Region {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut grid = Grid::<_, N>::fill('.');
+        // Only as large as needed to reach the region's furthest cells
+        let width = self.0.iter().map(|x| x.x() + 1).max().unwrap_or(0);
+        let height = self.0.iter().map(|x| x.y() + 1).max().unwrap_or(0);
+        let mut grid = Grid::fill(width, height, '.');
         self.0.iter().for_each(|coord| grid.set('X', *coord));
         write!(f, "{}", grid)
     }
 }
 
-pub fn run(input: String) {
-    const N: usize = 140;
-    //const N: usize = 6;
-    let garden = Garden::<N>::new(&input).expect("Unable to parse garden");
-    let regions = garden.regions();
-    let total: usize = regions.iter().map(|region| region.cost(&garden)).sum();
-    println!("Total cost: {}", total);
-    let total: usize = regions
-        .iter()
-        .map(|region| region.discount_cost(&garden))
-        .sum();
-    println!("Discounted cost is: {}", total);
+impl Solver for Garden {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Garden::new(input)
+    }
+
+    fn part1(&self) -> Result<Answer, SolveError> {
+        Ok(Self::total(&self.breakdown(1)).into())
+    }
+
+    fn part2(&self) -> Result<Answer, SolveError> {
+        Ok(Self::total(&self.breakdown(2)).into())
+    }
+
+    fn explain(&self, part: u8) -> Option<Result<(Answer, Explanation), SolveError>> {
+        let prices = self.breakdown(part);
+        let rows = prices
+            .iter()
+            .map(|x| {
+                vec![
+                    x.plant.to_string(),
+                    x.area.to_string(),
+                    x.fences.to_string(),
+                    x.price().to_string(),
+                ]
+            })
+            .collect();
+        let fences = if part == 1 { "Perimeter" } else { "Sides" };
+        let explanation = Explanation {
+            header: vec!["Plant", "Area", fences, "Price"],
+            rows,
+        };
+        Some(Ok((Self::total(&prices).into(), explanation)))
+    }
+
+    fn render(&self) -> Option<String> {
+        Some(self.0.to_string())
+    }
+}
+
+examples! {
+    Garden,
+    example: "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+" => (Some("1930"), Some("1206")),
 }
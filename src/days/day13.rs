@@ -1,3 +1,7 @@
+use super::{Answer, Explanation, Solver};
+use crate::error::{ParseError, SolveError};
+use crate::shared::parser::*;
+
 #[derive(Debug, Clone, Copy)]
 struct Vector(crate::shared::Vector<u64>);
 
@@ -9,12 +13,13 @@ impl std::ops::Deref for Vector {
 }
 
 impl Vector {
-    fn new(input: &str) -> Option<Self> {
-        let (x_str, y_str) = input.split_once(", ")?;
-        let x = str::parse(x_str.trim_start_matches(|c| !char::is_ascii_digit(&c))).ok()?;
-        let y = str::parse(y_str.trim_start_matches(|c| !char::is_ascii_digit(&c))).ok()?;
-
-        Some(Vector(crate::shared::Vector { x, y }))
+    // "X+94, Y+34" on a button line, "X=8400, Y=5400" on the prize line
+    fn parser<'a>(x: &'static str, y: &'static str) -> impl Parser<'a, Self> {
+        let coord = || named(int(), "a number");
+        map(
+            pair(preceded(literal(x), coord()), preceded(literal(y), coord())),
+            |(x, y)| Vector(crate::shared::Vector { x, y }),
+        )
     }
 
     fn signed(&self) -> (i64, i64) {
@@ -30,13 +35,16 @@ struct Claw {
 }
 
 impl Claw {
-    fn new(input: &str) -> Option<Self> {
-        let mut split = input.split("\n");
-        let a = Vector::new(split.next()?.trim_start_matches("Button A: "))?;
-        let b = Vector::new(split.next()?.trim_start_matches("Button B: "))?;
-        let prize = Vector::new(split.next()?.trim_start_matches("Prize: "))?;
-
-        Some(Claw { a, b, prize })
+    fn parser<'a>() -> impl Parser<'a, Self> {
+        let button = |label| {
+            let vector = Vector::parser("X+", ", Y+");
+            terminated(preceded(literal(label), vector), newline())
+        };
+        let prize = preceded(literal("Prize: "), Vector::parser("X=", ", Y="));
+        map(
+            pair(button("Button A: "), pair(button("Button B: "), prize)),
+            |(a, (b, prize))| Claw { a, b, prize },
+        )
     }
 
     fn solve(&self) -> Option<(u64, u64)> {
@@ -51,11 +59,6 @@ impl Claw {
         }
     }
 
-    fn cost(&self) -> Option<u64> {
-        let (a, b) = self.solve()?;
-        Some(a * 3 + b)
-    }
-
     fn solve_b_coeff(&self) -> Option<u64> {
         let (a_x, a_y) = self.a.signed();
         let (b_x, b_y) = self.b.signed();
@@ -85,23 +88,85 @@ impl Claw {
 
         long.solve()
     }
+}
+
+pub struct Claws(Vec<Claw>);
+
+impl Claws {
+    // A and B presses to win each claw's prize, if it can be won
+    fn breakdown(&self, part: u8) -> Vec<Option<(u64, u64)>> {
+        self.0
+            .iter()
+            .map(|claw| match part {
+                1 => claw.solve(),
+                _ => claw.solve_hard(),
+            })
+            .collect()
+    }
 
-    fn cost_hard(&self) -> Option<u64> {
-        let (a, b) = self.solve_hard()?;
-        Some(a * 3 + b)
+    // Tokens spent winning every prize that can be won
+    fn total(presses: &[Option<(u64, u64)>]) -> u64 {
+        presses.iter().flatten().map(|(a, b)| tokens(*a, *b)).sum()
     }
 }
 
-fn parse(input: &str) -> Option<Vec<Claw>> {
-    input.trim().split("\n\n").map(Claw::new).collect()
+// Cost of pressing A and B the given number of times
+fn tokens(a: u64, b: u64) -> u64 {
+    a * 3 + b
 }
 
-pub fn run(input: String) {
-    let claws = parse(&input).expect("Unable to parse claws");
+impl Solver for Claws {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Claws(parse_all(sections(Claw::parser()), input)?))
+    }
+
+    fn part1(&self) -> Result<Answer, SolveError> {
+        Ok(Self::total(&self.breakdown(1)).into())
+    }
+
+    fn part2(&self) -> Result<Answer, SolveError> {
+        Ok(Self::total(&self.breakdown(2)).into())
+    }
 
-    let cost: u64 = claws.iter().filter_map(|claw| claw.cost()).sum();
-    println!("Total cost: {cost}");
+    fn explain(&self, part: u8) -> Option<Result<(Answer, Explanation), SolveError>> {
+        let presses = self.breakdown(part);
+        let rows = presses
+            .iter()
+            .enumerate()
+            .map(|(i, presses)| {
+                let mut row = vec![(i + 1).to_string()];
+                match presses {
+                    Some((a, b)) => row.extend([*a, *b, tokens(*a, *b)].map(|x| x.to_string())),
+                    None => row.extend(["-", "-", "no win"].map(|x| x.to_string())),
+                }
+                row
+            })
+            .collect();
+        let explanation = Explanation {
+            header: vec!["Claw", "A presses", "B presses", "Tokens"],
+            rows,
+        };
+        Some(Ok((Self::total(&presses).into(), explanation)))
+    }
+}
 
-    let cost: u64 = claws.iter().filter_map(|claw| claw.cost_hard()).sum();
-    println!("Total cost of far locations: {cost}");
+examples! {
+    Claws,
+    example: "\
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+" => (Some("480"), None),
 }
@@ -1,3 +0,0 @@
-pub fn run(input: String) {
-    print!("{}", input)
-}
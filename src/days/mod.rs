@@ -1,58 +1,295 @@
-use core::panic;
-
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-
-pub fn run_day(day: i32, input: String) {
-    match day {
-        1 => day01::run(input),
-        2 => day02::run(input),
-        3 => day03::run(input),
-        4 => day04::run(input),
-        5 => day05::run(input),
-        6 => day06::run(input),
-        7 => day07::run(input),
-        8 => day08::run(input),
-        9 => day09::run(input),
-        10 => day10::run(input),
-        11 => day11::run(input),
-        12 => day12::run(input),
-        13 => day13::run(input),
-        14 => day14::run(input),
-        15 => day15::run(input),
-        16 => day16::run(input),
-        17 => day17::run(input),
-        18 => day18::run(input),
-        19 => day19::run(input),
-        20 => day20::run(input),
-        21 => day21::run(input),
-        22 => day22::run(input),
-        23 => day23::run(input),
-        24 => day24::run(input),
-        25 => day25::run(input),
-        _ => panic!("Invalid day, please select one between 1 and 25"),
+use std::fmt;
+
+use crate::error::{Error, ParseError, SolveError};
+
+// Declares a day's worked examples from the puzzle text, each parsed with the given solver and
+// checked by a generated test
+macro_rules! examples {
+    ($solver:ty, $($name:ident: $input:expr => ($part1:expr, $part2:expr)),* $(,)?) => {
+        pub const EXAMPLES: &[$crate::days::Example] = &[$(
+            $crate::days::Example {
+                name: stringify!($name),
+                input: $input,
+                part1: $part1,
+                part2: $part2,
+                parse: $crate::days::parse::<$solver>,
+            }
+        ),*];
+
+        #[cfg(test)]
+        mod examples {
+            $(
+                #[test]
+                fn $name() {
+                    let example = super::EXAMPLES
+                        .iter()
+                        .find(|x| x.name == stringify!($name))
+                        .unwrap();
+                    $crate::days::check_example(example);
+                }
+            )*
+        }
+    };
+}
+
+// Declares every day of the calendar with its title, and for solved days the module holding
+// the solver, generating the module declarations and the DAYS registry
+macro_rules! days {
+    (@parse) => { None };
+    (@parse $module:ident::$solver:ident) => { Some(parse::<$module::$solver> as Parser) };
+    (@examples) => { &[] };
+    (@examples $module:ident) => { $module::EXAMPLES };
+    ($($number:literal: $title:literal $(=> $module:ident::$solver:ident)?),* $(,)?) => {
+        $($(mod $module;)?)*
+
+        pub const DAYS: &[Day] = &[$(
+            Day {
+                number: $number,
+                title: $title,
+                parse: days!(@parse $($module::$solver)?),
+                examples: days!(@examples $($module)?),
+            }
+        ),*];
+    };
+}
+
+days! {
+    1: "Historian Hysteria" => day01::Lists,
+    2: "Red-Nosed Reports" => day02::Reports,
+    3: "Mull It Over" => day03::Program,
+    4: "Ceres Search" => day04::Puzzle,
+    5: "Print Queue" => day05::PrintJob,
+    6: "Guard Gallivant" => day06::Puzzle,
+    7: "Bridge Repair" => day07::Equations,
+    8: "Resonant Collinearity" => day08::Puzzle,
+    9: "Disk Fragmenter" => day09::DiskMap,
+    10: "Hoof It" => day10::Puzzle,
+    11: "Plutonian Pebbles" => day11::Stones,
+    12: "Garden Groups" => day12::Puzzle,
+    13: "Claw Contraption" => day13::Claws,
+    14: "Restroom Redoubt",
+    15: "Warehouse Woes",
+    16: "Reindeer Maze",
+    17: "Chronospatial Computer",
+    18: "RAM Run",
+    19: "Linen Layout",
+    20: "Race Condition",
+    21: "Keypad Conundrum",
+    22: "Monkey Market",
+    23: "LAN Party",
+    24: "Crossed Wires",
+    25: "Code Chronicle",
+}
+
+// Result of solving one part of a puzzle
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Str(String),
+}
+
+impl From<i64> for Answer {
+    fn from(val: i64) -> Self {
+        Answer::Int(val)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(val: u64) -> Self {
+        Answer::Int(val as i64)
     }
 }
+
+impl From<u32> for Answer {
+    fn from(val: u32) -> Self {
+        Answer::Int(val as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(val: usize) -> Self {
+        Answer::Int(val as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(val: String) -> Self {
+        Answer::Str(val)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(val) => write!(f, "{val}"),
+            Answer::Str(val) => write!(f, "{val}"),
+        }
+    }
+}
+
+// A day's puzzle, parsed from the input once and then solved part by part, possibly with the
+// parts on different threads
+pub trait Solver: Send + Sync {
+    fn parse(input: &str) -> Result<Self, ParseError>
+    where
+        Self: Sized;
+    fn part1(&self) -> Result<Answer, SolveError>;
+    fn part2(&self) -> Result<Answer, SolveError>;
+
+    // Answer to a part along with a breakdown of how it's reached, for solvers that have one.
+    // Both come from the same per item results, so the part is only solved once
+    fn explain(&self, _part: u8) -> Option<Result<(Answer, Explanation), SolveError>> {
+        None
+    }
+
+    // Text picture of the puzzle, for grid days
+    fn render(&self) -> Option<String> {
+        None
+    }
+}
+
+// Table of the per item results making up an answer, with a row per item
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explanation {
+    pub header: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+}
+
+// Which parts of a puzzle to solve
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    Both,
+}
+
+impl Part {
+    pub fn part1(&self) -> bool {
+        matches!(self, Part::One | Part::Both)
+    }
+
+    pub fn part2(&self) -> bool {
+        matches!(self, Part::Two | Part::Both)
+    }
+
+    // Numbers of the selected parts, in order
+    pub fn numbers(&self) -> Vec<u8> {
+        [(1, self.part1()), (2, self.part2())]
+            .into_iter()
+            .filter(|(_, selected)| *selected)
+            .map(|(part, _)| part)
+            .collect()
+    }
+}
+
+// A worked example input with the answers given in the puzzle text
+pub struct Example {
+    pub name: &'static str,
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+    pub parse: Parser,
+}
+
+impl Example {
+    // Solves the example for the given day, pairing each answer with the expected one for the
+    // parts that have one
+    pub fn run(&self, day: u32, part: Part) -> Result<Vec<(u8, Answer, &'static str)>, Error> {
+        let solver = (self.parse)(self.input).map_err(|error| Error::Parse { day, error })?;
+        let mut res = Vec::new();
+        for (number, expected) in [(1, self.part1), (2, self.part2)] {
+            if let Some(expected) = expected.filter(|_| part.numbers().contains(&number)) {
+                res.push((number, solve(day, solver.as_ref(), number)?, expected));
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+fn check_example(example: &Example) {
+    let results = example.run(0, Part::Both).unwrap();
+    for (part, answer, expected) in results {
+        assert_eq!(
+            answer.to_string(),
+            expected,
+            "part {part} of {}",
+            example.name
+        );
+    }
+}
+
+pub type Parser = fn(&str) -> Result<Box<dyn Solver>, ParseError>;
+
+fn parse<S: Solver + 'static>(input: &str) -> Result<Box<dyn Solver>, ParseError> {
+    let solver = S::parse(input).map_err(|why| why.with_source(input))?;
+    Ok(Box::new(solver))
+}
+
+// A day of the calendar, only solved days have a parser
+pub struct Day {
+    pub number: u32,
+    pub title: &'static str,
+    pub parse: Option<Parser>,
+    pub examples: &'static [Example],
+}
+
+impl Day {
+    pub fn is_implemented(&self) -> bool {
+        self.parse.is_some()
+    }
+}
+
+// Looks up a day in the registry
+pub fn day(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|x| x.number == number)
+}
+
+// Days with a solver, the remaining days of the calendar are still to be solved
+pub fn is_implemented(day: u32) -> bool {
+    self::day(day).is_some_and(Day::is_implemented)
+}
+
+// Parses the input for the given day
+pub fn parse_day(day: u32, input: &str) -> Result<Box<dyn Solver>, Error> {
+    let Some(entry) = self::day(day) else {
+        return Err(Error::InvalidDay(day));
+    };
+    let Some(parse) = entry.parse else {
+        return Err(Error::Unimplemented(day));
+    };
+    parse(input).map_err(|error| Error::Parse { day, error })
+}
+
+// Solves one part of a parsed day
+pub fn solve(day: u32, solver: &dyn Solver, part: u8) -> Result<Answer, Error> {
+    let answer = match part {
+        1 => solver.part1(),
+        2 => solver.part2(),
+        _ => Err(SolveError(format!(
+            "puzzles only have parts 1 and 2, not {part}"
+        ))),
+    };
+    answer.map_err(|error| Error::Solver { day, part, error })
+}
+
+// Solves one part of a parsed day along with its breakdown, if the solver has one
+pub fn solve_explained(
+    day: u32,
+    solver: &dyn Solver,
+    part: u8,
+) -> Result<(Answer, Option<Explanation>), Error> {
+    match solver.explain(part) {
+        Some(explained) => explained
+            .map(|(answer, explanation)| (answer, Some(explanation)))
+            .map_err(|error| Error::Solver { day, part, error }),
+        None => Ok((solve(day, solver, part)?, None)),
+    }
+}
+
+// Worked examples for the given day, empty if the day has no solver yet
+pub fn examples(day: u32) -> &'static [Example] {
+    self::day(day).map_or(&[], |x| x.examples)
+}
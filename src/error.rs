@@ -0,0 +1,262 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// Location and reason an input couldn't be parsed, lines and columns count from 1. The offending
+// token and the form that was expected are noted where the parser knows them
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    pub token: Option<String>,
+    pub expected: Option<String>,
+    // Text of the offending line, filled in once the whole input is at hand
+    pub source_line: Option<String>,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+            token: None,
+            expected: None,
+            source_line: None,
+        }
+    }
+
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    pub fn expected(mut self, expected: impl Into<String>) -> Self {
+        self.expected = Some(expected.into());
+        self
+    }
+
+    // Moves the error down by the given number of lines, for errors from parsing a later section
+    pub fn offset(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    // Keeps the offending line of the input the error was found in, for the snippet
+    pub fn with_source(mut self, input: &str) -> Self {
+        if self.source_line.is_none() {
+            self.source_line = input
+                .lines()
+                .nth(self.line.saturating_sub(1))
+                .map(String::from);
+        }
+        self
+    }
+
+    // Offending line with a caret under the token, or None if the line isn't known
+    pub fn snippet(&self) -> Option<String> {
+        let text = self.source_line.as_ref()?;
+        let number = self.line.to_string();
+        let pad = " ".repeat(number.len());
+        let width = self.token.as_ref().map_or(1, |x| x.chars().count().max(1));
+        let carets = "^".repeat(width);
+        let label = match &self.expected {
+            Some(expected) => format!("expected {expected}"),
+            None => self.message.clone(),
+        };
+        Some(format!(
+            "{pad} --> line {}, column {}\n{pad} |\n{number} | {text}\n{pad} | {}{carets} {label}",
+            self.line,
+            self.column,
+            " ".repeat(self.column.saturating_sub(1)),
+        ))
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if let Some(token) = &self.token {
+            write!(f, ", found '{token}'")?;
+        }
+        if let Some(expected) = &self.expected {
+            write!(f, ", expected {expected}")?;
+        }
+        Ok(())
+    }
+}
+
+// Reason a solver couldn't produce an answer from an otherwise valid input
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolveError(pub String);
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    Io {
+        path: PathBuf,
+        message: String,
+    },
+    Parse {
+        day: u32,
+        error: ParseError,
+    },
+    InvalidDay(u32),
+    Unimplemented(u32),
+    Solver {
+        day: u32,
+        part: u8,
+        error: SolveError,
+    },
+    // File that was read fine but can't be used as it is, line counts from 1 where known
+    InvalidFile {
+        path: PathBuf,
+        line: Option<usize>,
+        message: String,
+    },
+    Timeout {
+        day: u32,
+        limit: Duration,
+    },
+}
+
+impl Error {
+    pub fn io(path: &Path, why: io::Error) -> Self {
+        Error::Io {
+            path: path.to_path_buf(),
+            message: why.to_string(),
+        }
+    }
+
+    // Annotated snippet of the input for parse errors that know their line
+    pub fn snippet(&self) -> Option<String> {
+        match self {
+            Error::Parse { error, .. } => error.snippet(),
+            _ => None,
+        }
+    }
+
+    // Process exit code for the error, 1 and 2 are left for failed checks and usage errors
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Io { .. } => 3,
+            Error::Parse { .. } => 4,
+            Error::InvalidDay(_) => 5,
+            Error::Unimplemented(_) => 6,
+            Error::Solver { .. } => 7,
+            Error::InvalidFile { .. } => 8,
+            Error::Timeout { .. } => 9,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, message } => write!(f, "{}: {message}", path.display()),
+            Error::Parse { day, error } => write!(f, "day {day} input, {error}"),
+            Error::InvalidDay(day) => {
+                write!(f, "invalid day {day}, please select one between 1 and 25")
+            }
+            Error::Unimplemented(day) => write!(f, "day {day} is not implemented"),
+            Error::Solver { day, part, error } => write!(f, "day {day} part {part}, {error}"),
+            Error::InvalidFile {
+                path,
+                line: Some(line),
+                message,
+            } => write!(f, "{}:{line}: {message}", path.display()),
+            Error::InvalidFile {
+                path,
+                line: None,
+                message,
+            } => write!(f, "{}: {message}", path.display()),
+            Error::Timeout { day, limit } => write!(f, "day {day} timed out after {limit:?}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = ParseError::new(2, 5, "invalid digit")
+            .token("4x")
+            .expected("a level");
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 5: invalid digit, found '4x', expected a level"
+        );
+        assert_eq!(
+            ParseError::new(1, 1, "missing number").to_string(),
+            "line 1, column 1: missing number"
+        );
+        let error = Error::Parse {
+            day: 3,
+            error: ParseError::new(1, 2, "bad").expected("a digit"),
+        };
+        assert_eq!(
+            error.to_string(),
+            "day 3 input, line 1, column 2: bad, expected a digit"
+        );
+    }
+
+    #[test]
+    fn snippet_carets() {
+        let error = ParseError::new(2, 4, "invalid digit")
+            .token("éx")
+            .expected("a digit")
+            .with_source("1 2\nñé éx 3\n");
+        assert_eq!(
+            error.snippet().unwrap(),
+            "  --> line 2, column 4\n  |\n2 | ñé éx 3\n  |    ^^ expected a digit"
+        );
+
+        // Wider line numbers push the gutter along
+        let mut error = ParseError::new(1, 1, "bad").token("x").offset(11);
+        error.source_line = Some("x".to_string());
+        assert_eq!(
+            error.snippet().unwrap(),
+            "   --> line 12, column 1\n   |\n12 | x\n   | ^ bad"
+        );
+    }
+
+    #[test]
+    fn snippet_without_token_or_expected() {
+        // A single caret labelled with the message
+        let error = ParseError::new(1, 3, "missing line break").with_source("ab");
+        assert_eq!(
+            error.snippet().unwrap(),
+            "  --> line 1, column 3\n  |\n1 | ab\n  |   ^ missing line break"
+        );
+        let error = ParseError::new(1, 1, "bad")
+            .expected("a digit")
+            .with_source("x");
+        assert!(error.snippet().unwrap().ends_with("| ^ expected a digit"));
+    }
+
+    #[test]
+    fn snippet_without_source() {
+        let error = ParseError::new(1, 1, "bad").token("x");
+        assert_eq!(error.snippet(), None);
+        // The line isn't in the input
+        let error = ParseError::new(3, 1, "bad").with_source("x\ny");
+        assert_eq!(error.source_line, None);
+        assert_eq!(error.snippet(), None);
+        assert_eq!(Error::Unimplemented(3).snippet(), None);
+    }
+}
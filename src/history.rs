@@ -0,0 +1,260 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::process;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use advent_of_code_2024::Error;
+
+use crate::output::{print_table, Record};
+
+// One solved part from a past run, stored one per line as
+// "timestamp day part nanoseconds commit answer", with - when the commit isn't known
+#[derive(Debug, PartialEq, Eq)]
+struct Entry {
+    timestamp: u64,
+    day: u32,
+    part: u8,
+    time: Duration,
+    commit: Option<String>,
+    answer: String,
+}
+
+impl Entry {
+    fn parse(line: &str) -> Option<Self> {
+        let mut split = line.splitn(6, ' ');
+        let timestamp = str::parse(split.next()?).ok()?;
+        let day = str::parse(split.next()?).ok()?;
+        let part = str::parse(split.next()?).ok()?;
+        let time = Duration::from_nanos(str::parse(split.next()?).ok()?);
+        let commit = Some(split.next()?).filter(|x| *x != "-").map(String::from);
+        let answer = split.next()?.trim().to_string();
+        Some(Entry {
+            timestamp,
+            day,
+            part,
+            time,
+            commit,
+            answer,
+        })
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {}",
+            self.timestamp,
+            self.day,
+            self.part,
+            self.time.as_nanos(),
+            self.commit.as_deref().unwrap_or("-"),
+            self.answer
+        )
+    }
+}
+
+// Short hash of the commit the solvers were built from, if they're in a git checkout
+fn commit() -> Option<String> {
+    let output = process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .ok()?;
+    let hash = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    Some(hash).filter(|x| output.status.success() && !x.is_empty())
+}
+
+// Adds every solved part in the records to the history file
+pub fn append(path: &Path, records: &[Record]) -> Result<(), Error> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs());
+    let commit = commit();
+    let lines = records
+        .iter()
+        .filter_map(|x| {
+            let entry = Entry {
+                timestamp,
+                day: x.day,
+                part: x.part,
+                time: x.time?,
+                commit: commit.clone(),
+                answer: x.answer.as_ref()?.to_string(),
+            };
+            Some(format!("{entry}\n"))
+        })
+        .collect::<String>();
+    if lines.is_empty() {
+        return Ok(());
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|why| Error::io(path, why))?;
+    file.write_all(lines.as_bytes())
+        .map_err(|why| Error::io(path, why))
+}
+
+fn load(path: &Path) -> Result<Vec<Entry>, Error> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(path).map_err(|why| Error::io(path, why))?;
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(y, line)| {
+            Entry::parse(line).ok_or_else(|| Error::InvalidFile {
+                path: path.to_path_buf(),
+                line: Some(y + 1),
+                message: format!("invalid history line '{line}'"),
+            })
+        })
+        .collect()
+}
+
+// UTC date and time of a unix timestamp, as YYYY-MM-DD HH:MM
+pub fn format_timestamp(timestamp: u64) -> String {
+    let (days, secs) = ((timestamp / 86400) as i64, timestamp % 86400);
+    // Civil date from days since the epoch, counting in 400 year eras starting on 1st March
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60
+    )
+}
+
+// Change of the latest time against the first, as a percentage
+fn change(first: Duration, latest: Duration) -> String {
+    let old = first.as_secs_f64();
+    if old > 0.0 {
+        format!("{:+.1}%", (latest.as_secs_f64() - old) / old * 100.0)
+    } else {
+        "-".to_string()
+    }
+}
+
+// Prints how each selected part's runtime has changed across the recorded runs, flagging any
+// change in answer. With every run, each entry is listed rather than a summary per part
+pub fn run(selected: RangeInclusive<u32>, path: &Path, every_run: bool) -> Result<(), Error> {
+    let entries = load(path)?;
+    let mut parts = BTreeMap::<(u32, u8), Vec<&Entry>>::new();
+    for entry in entries.iter().filter(|x| selected.contains(&x.day)) {
+        parts
+            .entry((entry.day, entry.part))
+            .or_default()
+            .push(entry);
+    }
+
+    if every_run {
+        let mut rows = Vec::new();
+        for runs in parts.values() {
+            for (i, entry) in runs.iter().enumerate() {
+                let changed = i > 0 && runs[i - 1].answer != entry.answer;
+                rows.push(vec![
+                    entry.day.to_string(),
+                    entry.part.to_string(),
+                    format_timestamp(entry.timestamp),
+                    entry.commit.clone().unwrap_or("-".to_string()),
+                    format!("{:.2?}", entry.time),
+                    entry.answer.clone(),
+                    if changed { "CHANGED" } else { "" }.to_string(),
+                ]);
+            }
+        }
+        let header = ["Day", "Part", "When", "Commit", "Time", "Answer", ""];
+        print_table(&header, &rows);
+        return Ok(());
+    }
+
+    let rows = parts
+        .values()
+        .map(|runs| {
+            let (first, latest) = (runs[0], runs[runs.len() - 1]);
+            let best = runs.iter().map(|x| x.time).min().unwrap_or_default();
+            let changes = runs
+                .windows(2)
+                .filter(|x| x[0].answer != x[1].answer)
+                .count();
+            let answer = match changes {
+                0 => latest.answer.clone(),
+                1 => format!("{} (CHANGED once)", latest.answer),
+                _ => format!("{} (CHANGED {changes} times)", latest.answer),
+            };
+            vec![
+                latest.day.to_string(),
+                latest.part.to_string(),
+                runs.len().to_string(),
+                format!("{:.2?}", first.time),
+                format!("{:.2?}", best),
+                format!("{:.2?}", latest.time),
+                change(first.time, latest.time),
+                answer,
+            ]
+        })
+        .collect::<Vec<_>>();
+    let header = [
+        "Day", "Part", "Runs", "First", "Best", "Latest", "Change", "Answer",
+    ];
+    print_table(&header, &rows);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(951782400), "2000-02-29 00:00");
+        assert_eq!(format_timestamp(1709210096), "2024-02-29 12:34");
+        assert_eq!(format_timestamp(1735689599), "2024-12-31 23:59");
+        assert_eq!(format_timestamp(4107542400), "2100-03-01 00:00");
+    }
+
+    #[test]
+    fn entry_round_trip() {
+        let entry = Entry {
+            timestamp: 1733011200,
+            day: 7,
+            part: 2,
+            time: Duration::from_nanos(123456),
+            commit: Some("abc1234".to_string()),
+            answer: "two words".to_string(),
+        };
+        assert_eq!(entry.to_string(), "1733011200 7 2 123456 abc1234 two words");
+        assert_eq!(Entry::parse(&entry.to_string()), Some(entry));
+
+        let entry = Entry {
+            commit: None,
+            ..Entry::parse("1 1 1 0 - 42").unwrap()
+        };
+        assert_eq!(entry.to_string(), "1 1 1 0 - 42");
+        assert_eq!(Entry::parse(&entry.to_string()), Some(entry));
+    }
+
+    #[test]
+    fn invalid_entries() {
+        assert_eq!(Entry::parse("1 1 1 0 -"), None);
+        assert_eq!(Entry::parse("x 1 1 0 - 42"), None);
+        assert_eq!(Entry::parse("1 1 1 -5 - 42"), None);
+    }
+}
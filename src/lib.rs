@@ -0,0 +1,12 @@
+pub mod days;
+pub mod error;
+pub mod shared;
+
+pub use days::{Answer, Part, Solver};
+pub use error::Error;
+
+// Parses a day's input and solves one part of it
+pub fn solve(day: u32, part: u8, input: &str) -> Result<Answer, Error> {
+    let solver = days::parse_day(day, input)?;
+    days::solve(day, solver.as_ref(), part)
+}
@@ -1,49 +1,622 @@
-use clap::Parser;
-use core::panic;
-use std::fs::File;
+use clap::{CommandFactory, Parser, Subcommand};
+use std::collections::HashMap;
+use std::fs;
 use std::io;
 use std::io::Read;
-use std::path::Path;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
-mod days;
-pub mod shared;
+use advent_of_code_2024::shared::{self, Level, LogFilter};
+use advent_of_code_2024::{days, Error, Part, Solver};
+
+mod alloc;
+mod bench;
+mod history;
+mod output;
+mod pool;
+mod report;
+mod scaffold;
+mod verify;
+mod watch;
+
+use alloc::AllocStats;
+use output::{print_records, Format, Record};
 
 #[derive(Parser)]
 struct Args {
-    #[arg(short, long)]
-    day: i32,
+    /// Day to run, or a range of days such as 1..=13
+    #[arg(short, long, global = true, value_parser = parse_days)]
+    day: Option<RangeInclusive<u32>>,
 
-    #[arg(short, long, value_name = "FILE")]
+    /// Run every day
+    #[arg(short, long, global = true, conflicts_with = "day")]
+    all: bool,
+
+    /// Input file for a single day, or - to read from stdin
+    #[arg(short, long, global = true, value_name = "FILE")]
     input: Option<String>,
+
+    /// Directory of dayNN.txt input files used when --input is omitted
+    #[arg(
+        long,
+        global = true,
+        value_name = "DIR",
+        env = "AOC_INPUTS",
+        default_value = "inputs"
+    )]
+    inputs: PathBuf,
+
+    /// Run against the worked examples from the puzzle text instead of the inputs
+    #[arg(short, long, conflicts_with = "input")]
+    example: bool,
+
+    /// How to print the answers
+    #[arg(short, long, global = true, value_enum, default_value = "text")]
+    format: Format,
+
+    /// File each run's answers and timings are appended to, defaults to history.txt in the
+    /// inputs directory. Runs with --input aren't recorded
+    #[arg(long, global = true, value_name = "FILE", env = "AOC_HISTORY")]
+    history: Option<PathBuf>,
+
+    /// Number of threads to spread the days and their parts over
+    #[arg(short, long, global = true, value_name = "N", default_value_t = 1)]
+    jobs: usize,
+
+    /// Give up on a day that takes longer than this, such as 30s or 500ms
+    #[arg(long, global = true, value_name = "DURATION", value_parser = parse_duration)]
+    timeout: Option<Duration>,
+
+    /// Re-run the selected days whenever their input files change
+    #[arg(short, long, conflicts_with = "example")]
+    watch: bool,
+
+    /// Show a breakdown of how each answer was reached, for days that have one
+    #[arg(long, global = true)]
+    explain: bool,
+
+    /// Don't show progress while solving
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Show debug messages from the solvers, or trace messages as well when repeated
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Solver messages to show, a level and per day overrides such as debug,day10=trace
+    #[arg(long, global = true, value_name = "FILTER", env = "AOC_LOG")]
+    log: Option<LogFilter>,
+
+    /// Which parts of the puzzle to solve
+    #[arg(short, long, global = true, value_enum, default_value = "both")]
+    part: Part,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Time the parse, part 1 and part 2 phases of each selected day
+    Bench {
+        /// Untimed runs of each phase before measuring
+        #[arg(long, default_value_t = 1)]
+        warmup: usize,
+
+        /// Timed runs of each phase
+        #[arg(short = 'n', long, default_value_t = 10)]
+        iterations: usize,
+
+        /// Save the results as a baseline for later runs
+        #[arg(long, value_name = "FILE")]
+        save: Option<String>,
+
+        /// Compare the results against a previously saved baseline
+        #[arg(long, value_name = "FILE")]
+        baseline: Option<String>,
+
+        /// Percentage slowdown of the median over the baseline to flag as a regression
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Check each selected day's answers against the answers file
+    Verify {
+        /// Answers file, defaults to answers.txt in the inputs directory
+        #[arg(long, value_name = "FILE")]
+        answers: Option<PathBuf>,
+
+        /// Store answers for any parts missing from the answers file
+        #[arg(long)]
+        record: bool,
+    },
+    /// List the days of the calendar and which have been solved
+    List,
+    /// Write a report of the selected days' answers, timings and verification status
+    Report {
+        /// HTML file to write the report to
+        #[arg(long, value_name = "FILE")]
+        html: PathBuf,
+
+        /// Answers file, defaults to answers.txt in the inputs directory
+        #[arg(long, value_name = "FILE")]
+        answers: Option<PathBuf>,
+    },
+    /// Show how each part's runtime and answer changed over the recorded runs
+    History {
+        /// List every recorded run instead of a summary per part
+        #[arg(long)]
+        runs: bool,
+    },
+    /// Generate the module, registry entry and empty input file for a new day
+    New {
+        /// Crate directory to generate the day in
+        #[arg(long, value_name = "DIR", default_value = env!("CARGO_MANIFEST_DIR"))]
+        root: PathBuf,
+    },
+}
+
+// Parses a day selection, either a single day or a range like 1..=13 or 1..14
+fn parse_days(arg: &str) -> Result<RangeInclusive<u32>, String> {
+    let parse = |val: &str| {
+        str::parse::<u32>(val.trim()).map_err(|why| format!("invalid day '{val}': {why}"))
+    };
+
+    let range = if let Some((start, end)) = arg.split_once("..=") {
+        parse(start)?..=parse(end)?
+    } else if let Some((start, end)) = arg.split_once("..") {
+        parse(start)?..=parse(end)?.saturating_sub(1)
+    } else {
+        let day = parse(arg)?;
+        day..=day
+    };
+
+    if range.is_empty() || *range.start() < 1 || *range.end() > 25 {
+        Err("days must be between 1 and 25".to_string())
+    } else {
+        Ok(range)
+    }
+}
+
+// Parses a duration in seconds, or with a unit of ms, s or m
+fn parse_duration(arg: &str) -> Result<Duration, String> {
+    let (number, scale) = if let Some(ms) = arg.strip_suffix("ms") {
+        (ms, 0.001)
+    } else if let Some(s) = arg.strip_suffix('s') {
+        (s, 1.0)
+    } else if let Some(m) = arg.strip_suffix('m') {
+        (m, 60.0)
+    } else {
+        (arg, 1.0)
+    };
+
+    match str::parse::<f64>(number.trim()) {
+        Ok(val) if val.is_finite() && val > 0.0 => Ok(Duration::from_secs_f64(val * scale)),
+        _ => Err(format!(
+            "invalid duration '{arg}', expected something like 30s or 500ms"
+        )),
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|why| Error::io(path, why))
 }
 
 fn main() {
     let args = Args::parse();
+    if let Err(why) = run(&args) {
+        eprintln!("error: {why}");
+        if let Some(snippet) = why.snippet() {
+            eprintln!("{snippet}");
+        }
+        std::process::exit(why.exit_code())
+    }
+}
 
-    let input = match args.input {
-        None => {
-            // Read input from stdin
-            let mut buffer = String::new();
-            if let Err(why) = io::stdin().read_to_string(&mut buffer) {
-                panic!("Couldn't read from stdin: {}", why)
+fn run(args: &Args) -> Result<(), Error> {
+    shared::set_quiet(args.quiet);
+    let mut filter = args.log.clone().unwrap_or_default();
+    filter.level = match args.verbose {
+        0 => filter.level,
+        1 => filter.level.max(Level::Debug),
+        _ => Level::Trace,
+    };
+    shared::set_log_filter(filter);
+
+    let days = match &args.day {
+        Some(days) => days.clone(),
+        None if args.all
+            || matches!(
+                args.command,
+                Some(Command::List | Command::History { .. } | Command::Report { .. })
+            ) =>
+        {
+            1..=25
+        }
+        None => Args::command()
+            .error(
+                clap::error::ErrorKind::MissingRequiredArgument,
+                "either --day or --all is required",
+            )
+            .exit(),
+    };
+    let single = days.start() == days.end() && !args.all;
+    if !single && args.input.is_some() {
+        Args::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "--input can only be used when running a single day",
+            )
+            .exit()
+    }
+    let new = matches!(args.command, Some(Command::New { .. }));
+    if new && !single {
+        Args::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "new generates a single day, pass it with --day",
+            )
+            .exit()
+    }
+    // Listing, reporting and scaffolding make sense for days without a solver
+    let solves = matches!(
+        args.command,
+        None | Some(Command::Bench { .. } | Command::Verify { .. })
+    );
+    if single && solves && !days::is_implemented(*days.start()) {
+        return Err(Error::Unimplemented(*days.start()));
+    }
+
+    match args.command {
+        None if args.example => {
+            if !verify::run_examples(days, args) {
+                std::process::exit(1)
+            }
+        }
+        None if args.watch => {
+            if args.input.as_deref() == Some("-") {
+                Args::command()
+                    .error(
+                        clap::error::ErrorKind::ArgumentConflict,
+                        "--watch needs an input file to watch, not stdin",
+                    )
+                    .exit()
+            }
+            watch::run(days, args)
+        }
+        None if single => run_single(*days.start(), args)?,
+        None => run_summary(days, args)?,
+        Some(Command::Bench {
+            warmup,
+            iterations,
+            ref save,
+            ref baseline,
+            threshold,
+        }) => {
+            let options = bench::Options {
+                warmup,
+                iterations,
+                threshold,
+            };
+            bench::run(days, args, &options, save.as_deref(), baseline.as_deref())?
+        }
+        Some(Command::Verify {
+            ref answers,
+            record,
+        }) => {
+            let path = match answers {
+                Some(path) => path.clone(),
+                None => args.inputs.join("answers.txt"),
+            };
+            if !verify::run(days, args, &path, record)? {
+                std::process::exit(1)
             }
-            buffer
-        }
-        Some(path) => {
-            // Read input from file
-            let path = Path::new(&path);
-            let display = path.display();
-            let mut file = match File::open(path) {
-                Err(why) => panic!("couldn't open {}: {}", display, why),
-                Ok(file) => file,
+        }
+        Some(Command::List) => list(days),
+        Some(Command::Report {
+            ref html,
+            ref answers,
+        }) => {
+            let answers = match answers {
+                Some(path) => path.clone(),
+                None => args.inputs.join("answers.txt"),
             };
-            let mut s = String::new();
-            if let Err(why) = file.read_to_string(&mut s) {
-                panic!("couldn't read {}: {}", display, why)
+            report::run(days, args, html, &answers)?
+        }
+        Some(Command::History { runs }) => history::run(days, &history_path(args), runs)?,
+        Some(Command::New { ref root }) => scaffold::run(*days.start(), root, &args.inputs)?,
+    }
+    Ok(())
+}
+
+// Prints the title of each selected day and whether it has a solver
+fn list(selected: RangeInclusive<u32>) {
+    let rows = days::DAYS
+        .iter()
+        .filter(|x| selected.contains(&x.number))
+        .map(|x| {
+            let status = if x.is_implemented() {
+                "solved"
+            } else {
+                "not implemented"
             };
-            s
+            vec![
+                x.number.to_string(),
+                x.title.to_string(),
+                status.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    output::print_table(&["Day", "Title", "Status"], &rows);
+}
+
+// Reads the input for a day from the given file, stdin if the file is -, otherwise from
+// dayNN.txt in the inputs directory
+fn read_input(day: u32, args: &Args) -> Result<String, Error> {
+    match args.input.as_deref() {
+        Some("-") => {
+            // Read input from stdin
+            let mut buffer = String::new();
+            io::stdin()
+                .read_to_string(&mut buffer)
+                .map_err(|why| Error::io(Path::new("<stdin>"), why))?;
+            Ok(buffer)
+        }
+        // Read input from file
+        Some(path) => read_file(Path::new(path)),
+        None => read_file(&args.inputs.join(format!("day{day:02}.txt"))),
+    }
+}
+
+fn run_single(day: u32, args: &Args) -> Result<(), Error> {
+    let records = collect_records(day..=day, args);
+    if args.format != Format::Text {
+        print_records(&records, args.format);
+    }
+
+    let mut failure = None;
+    for record in &records {
+        match (&record.answer, &record.error) {
+            (Some(answer), _) if args.format == Format::Text => {
+                println!("Part {}: {answer}", record.part);
+                if let Some(explanation) = &record.explanation {
+                    output::print_table(&explanation.header, &explanation.rows);
+                }
+            }
+            (_, Some(why)) => {
+                failure.get_or_insert(why.clone());
+            }
+            _ => {}
+        }
+    }
+    if args.format == Format::Text {
+        output::print_allocations(&records);
+    }
+
+    match failure {
+        Some(why @ Error::Io { .. }) if args.input.is_none() => {
+            eprintln!("error: no input for day {day}, {why}");
+            eprintln!("hint: pass --input, or set --inputs or AOC_INPUTS");
+            std::process::exit(why.exit_code())
+        }
+        Some(why) => Err(why),
+        None => Ok(()),
+    }
+}
+
+// Runs each day against its input file, printing answers and timings, then fails with the
+// first parse or solver error. Days without an input or a solution are only noted in the output
+fn run_summary(selected: RangeInclusive<u32>, args: &Args) -> Result<(), Error> {
+    let records = collect_records(selected, args);
+    print_records(&records, args.format);
+
+    let failure = records.into_iter().find_map(|x| match x.error {
+        Some(Error::Io { .. } | Error::Unimplemented(_)) | None => None,
+        error => error,
+    });
+    match failure {
+        Some(why) => Err(why),
+        None => Ok(()),
+    }
+}
+
+// Runs the selected days, spread over a pool of threads when more than one job is allowed,
+// returning the records in day and part order and adding them to the history
+fn collect_records(selected: RangeInclusive<u32>, args: &Args) -> Vec<Record> {
+    let records = if args.jobs <= 1 {
+        selected.flat_map(|day| run_records(day, args)).collect()
+    } else {
+        run_parallel(selected, args)
+    };
+
+    // History follows the inputs directory, a one-off input file would look like a changed answer.
+    // The run still counts if it can't be recorded
+    if args.input.is_none() {
+        if let Err(why) = history::append(&history_path(args), &records) {
+            eprintln!("warning: couldn't record history, {why}");
+        }
+    }
+    records
+}
+
+fn run_parallel(selected: RangeInclusive<u32>, args: &Args) -> Vec<Record> {
+    let records = Mutex::new(Vec::new());
+    let records_ref = &records;
+    pool::run(args.jobs, |queue| {
+        for day in selected {
+            queue.push(move |queue| {
+                // With a timeout each day already runs on a worker of its own
+                if args.timeout.is_some() {
+                    records_ref.lock().unwrap().extend(run_records(day, args));
+                    return;
+                }
+
+                let parsed = load(day, args).and_then(|input| parse_timed(day, &input));
+                let (solver, parse) = match parsed {
+                    Ok(parsed) => parsed,
+                    Err(why) => {
+                        let failed = failed(day, &args.part.numbers(), why);
+                        records_ref.lock().unwrap().extend(failed);
+                        return;
+                    }
+                };
+
+                // Each part gets a task of its own, sharing the parsed input
+                let solver: Arc<dyn Solver> = solver.into();
+                for part in args.part.numbers() {
+                    let solver = solver.clone();
+                    queue.push(move |_| {
+                        let record = solve_part(day, solver.as_ref(), part, parse, args.explain);
+                        records_ref.lock().unwrap().push(record);
+                    });
+                }
+            });
+        }
+    });
+
+    let mut records = records.into_inner().unwrap();
+    records.sort_by_key(|x| (x.day, x.part));
+    records
+}
+
+fn history_path(args: &Args) -> PathBuf {
+    match &args.history {
+        Some(path) => path.clone(),
+        None => args.inputs.join("history.txt"),
+    }
+}
+
+fn failed(day: u32, parts: &[u8], error: Error) -> Vec<Record> {
+    parts
+        .iter()
+        .map(|part| Record::failed(day, *part, error.clone()))
+        .collect()
+}
+
+// Input for a day that has a solver
+fn load(day: u32, args: &Args) -> Result<String, Error> {
+    if !days::is_implemented(day) {
+        return Err(Error::Unimplemented(day));
+    }
+    read_input(day, args)
+}
+
+// Time and allocations taken by a phase
+type Measured = (Duration, Option<AllocStats>);
+
+fn parse_timed(day: u32, input: &str) -> Result<(Box<dyn Solver>, Measured), Error> {
+    let (solver, parse) = timed(|| days::parse_day(day, input));
+    Ok((solver?, parse))
+}
+
+fn solve_part(
+    day: u32,
+    solver: &dyn Solver,
+    part: u8,
+    (parse_time, parse_alloc): Measured,
+    explain: bool,
+) -> Record {
+    let (solved, (time, alloc)) = timed(|| {
+        if explain {
+            days::solve_explained(day, solver, part)
+        } else {
+            days::solve(day, solver, part).map(|answer| (answer, None))
+        }
+    });
+    let (answer, explanation) = match solved {
+        Ok((answer, explanation)) => (Ok(answer), explanation),
+        Err(why) => (Err(why), None),
+    };
+    Record {
+        day,
+        part,
+        answer: answer.as_ref().ok().cloned(),
+        parse_time: Some(parse_time),
+        time: Some(time),
+        parse_alloc,
+        alloc,
+        error: answer.err(),
+        explanation,
+    }
+}
+
+// Runs the selected parts of a day, timing each phase and noting why a day couldn't be run
+fn run_records(day: u32, args: &Args) -> Vec<Record> {
+    let parts = args.part.numbers();
+    let input = match load(day, args) {
+        Ok(input) => input,
+        Err(why) => return failed(day, &parts, why),
+    };
+
+    match args.timeout {
+        Some(limit) => solve_with_timeout(day, input, parts, args.explain, limit),
+        None => {
+            let mut records = Vec::new();
+            solve_parts(day, &input, &parts, args.explain, &mut |x| records.push(x));
+            records
+        }
+    }
+}
+
+// Parses the input and solves each part, passing on the record for each part as it finishes
+fn solve_parts(day: u32, input: &str, parts: &[u8], explain: bool, emit: &mut dyn FnMut(Record)) {
+    let (solver, parse) = match parse_timed(day, input) {
+        Ok(parsed) => parsed,
+        Err(why) => {
+            failed(day, parts, why).into_iter().for_each(emit);
+            return;
         }
     };
 
-    days::run_day(args.day, input);
+    for part in parts {
+        emit(solve_part(day, solver.as_ref(), *part, parse, explain));
+    }
+}
+
+// Solves the day on a worker thread, giving up on any parts not finished within the limit. The
+// worker can't be stopped, it is left running in the background until the process exits
+fn solve_with_timeout(
+    day: u32,
+    input: String,
+    parts: Vec<u8>,
+    explain: bool,
+    limit: Duration,
+) -> Vec<Record> {
+    let (sender, receiver) = mpsc::channel();
+    let worker_parts = parts.clone();
+    thread::spawn(move || {
+        solve_parts(day, &input, &worker_parts, explain, &mut |x| {
+            let _ = sender.send(x);
+        })
+    });
+
+    // Records are filed by their part rather than the order they arrive in, any part without one
+    // by the deadline timed out
+    let deadline = Instant::now() + limit;
+    let mut received = HashMap::new();
+    while received.len() < parts.len() {
+        let wait = deadline.saturating_duration_since(Instant::now());
+        match receiver.recv_timeout(wait) {
+            Ok(record) => {
+                received.insert(record.part, record);
+            }
+            Err(_) => break,
+        }
+    }
+    parts
+        .into_iter()
+        .map(|part| {
+            received
+                .remove(&part)
+                .unwrap_or_else(|| Record::failed(day, part, Error::Timeout { day, limit }))
+        })
+        .collect()
+}
+
+fn timed<T>(func: impl FnOnce() -> T) -> (T, Measured) {
+    let start = Instant::now();
+    let (res, alloc) = alloc::measure(func);
+    (res, (start.elapsed(), alloc))
 }
@@ -0,0 +1,337 @@
+use std::time::Duration;
+
+use advent_of_code_2024::days::Explanation;
+use advent_of_code_2024::{Answer, Error};
+
+use crate::alloc::AllocStats;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+// Outcome of running one part of a day
+pub struct Record {
+    pub day: u32,
+    pub part: u8,
+    pub answer: Option<Answer>,
+    pub parse_time: Option<Duration>,
+    pub time: Option<Duration>,
+    pub parse_alloc: Option<AllocStats>,
+    pub alloc: Option<AllocStats>,
+    pub error: Option<Error>,
+    pub explanation: Option<Explanation>,
+}
+
+impl Record {
+    // Record for a part that couldn't be solved
+    pub fn failed(day: u32, part: u8, error: Error) -> Self {
+        Record {
+            day,
+            part,
+            answer: None,
+            parse_time: None,
+            time: None,
+            parse_alloc: None,
+            alloc: None,
+            error: Some(error),
+            explanation: None,
+        }
+    }
+}
+
+pub fn print_records(records: &[Record], format: Format) {
+    match format {
+        Format::Text => print_text(records),
+        Format::Json => println!("{}", to_json(records)),
+        Format::Csv => print!("{}", to_csv(records)),
+    }
+}
+
+// Prints a table with a row per day, timed over parsing and every part that ran
+fn print_text(records: &[Record]) {
+    let mut rows = Vec::new();
+    for day_records in records.chunk_by(|l, r| l.day == r.day) {
+        let day = day_records[0].day.to_string();
+        let Some(parse_time) = day_records[0].parse_time else {
+            // Day never got as far as parsing, show why
+            let error = day_records[0].error.as_ref().map(describe);
+            rows.push(vec![day, error.unwrap_or_default()]);
+            continue;
+        };
+
+        let cell = |part| {
+            let record = day_records.iter().find(|x| x.part == part);
+            match record {
+                Some(Record {
+                    answer: Some(answer),
+                    ..
+                }) => answer.to_string(),
+                Some(Record {
+                    error: Some(error), ..
+                }) => describe(error),
+                _ => "-".to_string(),
+            }
+        };
+        let time = parse_time + day_records.iter().filter_map(|x| x.time).sum::<Duration>();
+        rows.push(vec![day, cell(1), cell(2), format!("{time:.2?}")]);
+    }
+
+    print_table(&["Day", "Part 1", "Part 2", "Time"], &rows);
+    print_allocations(records);
+
+    for record in records {
+        if let Some(explanation) = &record.explanation {
+            println!("\nDay {} part {}", record.day, record.part);
+            print_table(&explanation.header, &explanation.rows);
+        }
+    }
+}
+
+// Prints the allocations of each phase, only gathered when built with the alloc-stats feature
+pub fn print_allocations(records: &[Record]) {
+    let mut rows = Vec::new();
+    for day_records in records.chunk_by(|l, r| l.day == r.day) {
+        let day = day_records[0].day;
+        let parse = day_records[0].parse_alloc.map(|x| ("parse".to_string(), x));
+        let parts = day_records
+            .iter()
+            .filter_map(|x| Some((format!("part{}", x.part), x.alloc?)));
+        for (phase, stats) in parse.into_iter().chain(parts) {
+            rows.push(vec![
+                day.to_string(),
+                phase,
+                stats.count.to_string(),
+                stats.bytes.to_string(),
+                stats.peak.to_string(),
+            ]);
+        }
+    }
+
+    if !rows.is_empty() {
+        println!();
+        print_table(
+            &["Day", "Phase", "Allocations", "Bytes", "Peak bytes"],
+            &rows,
+        );
+    }
+}
+
+// Short form of an error for a table cell, the day is already in its row
+fn describe(error: &Error) -> String {
+    match error {
+        Error::Unimplemented(_) => "not implemented".to_string(),
+        Error::Timeout { .. } => "TIMEOUT".to_string(),
+        Error::Parse { error, .. } => format!("parse error at {error}"),
+        Error::Solver { error, .. } => format!("error: {error}"),
+        error => error.to_string(),
+    }
+}
+
+pub fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let mut widths = header.iter().map(|x| x.len()).collect::<Vec<_>>();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| format!("{:width$}", cells.get(i).unwrap_or(&"")))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", format_row(header.to_vec()));
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-+-")
+    );
+    for row in rows {
+        println!("{}", format_row(row.iter().map(|x| &x[..]).collect()));
+    }
+}
+
+// Serialisation
+fn json_string(val: &str) -> String {
+    let mut res = String::from("\"");
+    for c in val.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            c if (c as u32) < 0x20 => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+fn json_answer(answer: &Option<Answer>) -> String {
+    match answer {
+        Some(Answer::Int(val)) => val.to_string(),
+        Some(Answer::Str(val)) => json_string(val),
+        None => "null".to_string(),
+    }
+}
+
+fn json_explanation(explanation: &Option<Explanation>) -> String {
+    let Some(explanation) = explanation else {
+        return "null".to_string();
+    };
+    let list = |cells: Vec<String>| format!("[{}]", cells.join(","));
+    let header = explanation.header.iter().map(|x| json_string(x)).collect();
+    let rows = explanation
+        .rows
+        .iter()
+        .map(|row| list(row.iter().map(|x| json_string(x)).collect()))
+        .collect();
+    format!("{{\"header\":{},\"rows\":{}}}", list(header), list(rows))
+}
+
+fn json_alloc(stats: Option<AllocStats>) -> String {
+    stats.map_or("null".to_string(), |x| {
+        format!(
+            "{{\"count\":{},\"bytes\":{},\"peak\":{}}}",
+            x.count, x.bytes, x.peak
+        )
+    })
+}
+
+fn json_nanos(time: Option<Duration>) -> String {
+    time.map_or("null".to_string(), |x| x.as_nanos().to_string())
+}
+
+pub fn to_json(records: &[Record]) -> String {
+    let records = records
+        .iter()
+        .map(|x| {
+            format!(
+                "{{\"day\":{},\"part\":{},\"answer\":{},\"parse_ns\":{},\"time_ns\":{},\"parse_alloc\":{},\"alloc\":{},\"error\":{},\"explanation\":{}}}",
+                x.day,
+                x.part,
+                json_answer(&x.answer),
+                json_nanos(x.parse_time),
+                json_nanos(x.time),
+                json_alloc(x.parse_alloc),
+                json_alloc(x.alloc),
+                x.error
+                    .as_ref()
+                    .map_or("null".to_string(), |x| json_string(&x.to_string())),
+                json_explanation(&x.explanation)
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", records.join(","))
+}
+
+fn csv_field(val: &str) -> String {
+    if val.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", val.replace('"', "\"\""))
+    } else {
+        val.to_string()
+    }
+}
+
+pub fn to_csv(records: &[Record]) -> String {
+    let mut res = String::from("day,part,answer,parse_ns,time_ns,error\n");
+    for x in records {
+        let nanos = |time: Option<Duration>| time.map(|x| x.as_nanos().to_string());
+        let fields = [
+            x.day.to_string(),
+            x.part.to_string(),
+            x.answer.as_ref().map(|x| x.to_string()).unwrap_or_default(),
+            nanos(x.parse_time).unwrap_or_default(),
+            nanos(x.time).unwrap_or_default(),
+            x.error.as_ref().map(|x| x.to_string()).unwrap_or_default(),
+        ];
+        let line = fields.map(|x| csv_field(&x)).join(",");
+        res.push_str(&line);
+        res.push('\n');
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2024::error::ParseError;
+
+    fn solved(answer: Answer) -> Record {
+        Record {
+            day: 1,
+            part: 2,
+            answer: Some(answer),
+            parse_time: Some(Duration::from_nanos(5)),
+            time: Some(Duration::from_nanos(7)),
+            parse_alloc: None,
+            alloc: None,
+            error: None,
+            explanation: None,
+        }
+    }
+
+    #[test]
+    fn json_string_escapes() {
+        assert_eq!(json_string(r#"a"b\c"#), r#""a\"b\\c""#);
+        assert_eq!(json_string("a\nb\r\tc"), r#""a\nb\r\tc""#);
+        assert_eq!(json_string("\u{1}\u{1f}"), r#""\u0001\u001f""#);
+        assert_eq!(json_string("é"), "\"é\"");
+    }
+
+    #[test]
+    fn csv_field_quotes() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+        assert_eq!(csv_field("a\rb"), "\"a\rb\"");
+    }
+
+    #[test]
+    fn json_records() {
+        let records = [solved(Answer::Int(-3)), solved(Answer::Str("a\"b".into()))];
+        assert_eq!(
+            to_json(&records),
+            "[{\"day\":1,\"part\":2,\"answer\":-3,\"parse_ns\":5,\"time_ns\":7,\"parse_alloc\":null,\"alloc\":null,\"error\":null,\"explanation\":null},\
+             {\"day\":1,\"part\":2,\"answer\":\"a\\\"b\",\"parse_ns\":5,\"time_ns\":7,\"parse_alloc\":null,\"alloc\":null,\"error\":null,\"explanation\":null}]"
+        );
+    }
+
+    #[test]
+    fn json_failed_record() {
+        let error = Error::Parse {
+            day: 3,
+            error: ParseError::new(2, 4, "bad \"x\""),
+        };
+        assert_eq!(
+            to_json(&[Record::failed(3, 1, error)]),
+            "[{\"day\":3,\"part\":1,\"answer\":null,\"parse_ns\":null,\"time_ns\":null,\"parse_alloc\":null,\"alloc\":null,\"error\":\"day 3 input, line 2, column 4: bad \\\"x\\\"\",\"explanation\":null}]"
+        );
+    }
+
+    #[test]
+    fn csv_records() {
+        let failed = Record::failed(3, 1, Error::Unimplemented(3));
+        assert_eq!(
+            to_csv(&[solved(Answer::Str("a,\"b\"".into())), failed]),
+            "day,part,answer,parse_ns,time_ns,error\n\
+             1,2,\"a,\"\"b\"\"\",5,7,\n\
+             3,1,,,,day 3 is not implemented\n"
+        );
+    }
+}
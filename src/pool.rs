@@ -0,0 +1,78 @@
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+type Task<'a> = Box<dyn FnOnce(&Queue<'a>) + Send + 'a>;
+
+// Tasks waiting for a thread, and how many are running and may still queue more
+struct State<'a> {
+    tasks: VecDeque<Task<'a>>,
+    running: usize,
+}
+
+pub struct Queue<'a> {
+    state: Mutex<State<'a>>,
+    changed: Condvar,
+}
+
+impl<'a> Queue<'a> {
+    pub fn push(&self, task: impl FnOnce(&Queue<'a>) + Send + 'a) {
+        let mut state = self.state.lock().unwrap();
+        state.tasks.push_back(Box::new(task));
+        self.changed.notify_one();
+    }
+
+    // Next task to run, or None once the queue is empty and no running task can add to it
+    fn next(&self) -> Option<Task<'a>> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(task) = state.tasks.pop_front() {
+                state.running += 1;
+                return Some(task);
+            }
+            if state.running == 0 {
+                return None;
+            }
+            state = self.changed.wait(state).unwrap();
+        }
+    }
+
+    fn done(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.running -= 1;
+        self.changed.notify_all();
+    }
+}
+
+struct Running<'q, 'a>(&'q Queue<'a>);
+
+impl Drop for Running<'_, '_> {
+    fn drop(&mut self) {
+        self.0.done();
+    }
+}
+
+// Runs the queued tasks, and any tasks they queue in turn, on the given number of threads,
+// returning once every task has finished
+pub fn run<'a>(jobs: usize, seed: impl FnOnce(&Queue<'a>)) {
+    let queue = Queue {
+        state: Mutex::new(State {
+            tasks: VecDeque::new(),
+            running: 0,
+        }),
+        changed: Condvar::new(),
+    };
+    seed(&queue);
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            scope.spawn(|| {
+                while let Some(task) = queue.next() {
+                    // Marks the task done even if it panics, so the other threads don't wait on it
+                    let _running = Running(&queue);
+                    task(&queue);
+                }
+            });
+        }
+    });
+}
@@ -0,0 +1,135 @@
+use std::fmt::Write;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use advent_of_code_2024::{days, Error};
+
+use crate::history::format_timestamp;
+use crate::output::Record;
+use crate::verify::{AnswerFile, Status};
+use crate::{collect_records, read_input, Args};
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em auto; max-width: 70em; color: #222; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+th { background: #eee; }
+td.time { text-align: right; font-family: monospace; }
+.PASS { color: #171; font-weight: bold; }
+.FAIL { color: #b11; font-weight: bold; }
+.MISSING, .muted { color: #888; }
+pre { font-size: 8px; line-height: 1; background: #f6f6f6; padding: 1em; overflow-x: auto; }
+";
+
+fn escape(val: &str) -> String {
+    val.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn time_cell(time: Option<Duration>) -> String {
+    let time = time.map_or("-".to_string(), |x| format!("{x:.2?}"));
+    format!("<td class=\"time\">{time}</td>")
+}
+
+// Answer, verification status and time cells for one part of a day
+fn part_cells(record: Option<&Record>, known: &AnswerFile) -> String {
+    let Some(record) = record else {
+        return "<td class=\"muted\">-</td><td></td>".to_string() + &time_cell(None);
+    };
+    let (answer, status) = match (&record.answer, &record.error) {
+        (Some(answer), _) => (
+            answer.to_string(),
+            known.check(record.day, record.part, answer),
+        ),
+        (None, error) => (
+            error.as_ref().map(|x| x.to_string()).unwrap_or_default(),
+            Status::Fail,
+        ),
+    };
+    format!(
+        "<td>{}</td><td class=\"{status}\">{status}</td>{}",
+        escape(&answer),
+        time_cell(record.time)
+    )
+}
+
+fn table(records: &[Record], known: &AnswerFile) -> String {
+    let mut html = String::from(
+        "<table>\n<tr><th>Day</th><th>Title</th><th>Parse</th>\
+         <th>Part 1</th><th>Status</th><th>Time</th>\
+         <th>Part 2</th><th>Status</th><th>Time</th></tr>\n",
+    );
+    for day_records in records.chunk_by(|l, r| l.day == r.day) {
+        let day = day_records[0].day;
+        let title = days::day(day).map_or("", |x| x.title);
+        let _ = write!(html, "<tr><td>{day}</td><td>{}</td>", escape(title));
+
+        let Some(parse_time) = day_records[0].parse_time else {
+            // Day never got as far as parsing, show why across the rest of the row
+            let why = day_records[0].error.as_ref().map(|x| x.to_string());
+            let why = escape(&why.unwrap_or_default());
+            let _ = writeln!(html, "<td colspan=\"7\" class=\"muted\">{why}</td></tr>");
+            continue;
+        };
+        html.push_str(&time_cell(Some(parse_time)));
+        for part in [1, 2] {
+            let record = day_records.iter().find(|x| x.part == part);
+            html.push_str(&part_cells(record, known));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+// Pictures of each grid day's puzzle, skipping days without an input
+fn grids(selected: RangeInclusive<u32>, args: &Args) -> String {
+    let mut html = String::new();
+    for day in selected.filter(|day| days::is_implemented(*day)) {
+        let Ok(input) = read_input(day, args) else {
+            continue;
+        };
+        let Some(render) = days::parse_day(day, &input).ok().and_then(|x| x.render()) else {
+            continue;
+        };
+        let title = days::day(day).map_or("", |x| x.title);
+        let _ = writeln!(
+            html,
+            "<h2>Day {day}: {}</h2>\n<pre>{}</pre>",
+            escape(title),
+            escape(&render)
+        );
+    }
+    html
+}
+
+// Runs the selected days and writes a self-contained HTML page of their answers, timings,
+// verification against the answers file and grid pictures
+pub fn run(
+    selected: RangeInclusive<u32>,
+    args: &Args,
+    path: &Path,
+    answers: &Path,
+) -> Result<(), Error> {
+    let known = AnswerFile::load(answers)?;
+    let records = collect_records(selected.clone(), args);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs());
+
+    let html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Advent of Code 2024</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1>Advent of Code 2024</h1>\n<p class=\"muted\">Generated {} UTC</p>\n{}{}</body>\n</html>\n",
+        format_timestamp(timestamp),
+        table(&records, &known),
+        grids(selected, args)
+    );
+    fs::write(path, html).map_err(|why| Error::io(path, why))?;
+    println!("Wrote report to {}", path.display());
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use std::fs;
+use std::path::Path;
+
+use advent_of_code_2024::{days, Error};
+
+// Starting point for a new day, {title} is replaced with the puzzle title
+const TEMPLATE: &str = r#"use super::{Answer, Solver};
+use crate::error::{ParseError, SolveError};
+
+// {title}
+pub struct Puzzle {
+    lines: Vec<String>,
+}
+
+fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    Ok(input.lines().map(|line| line.to_string()).collect())
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Puzzle {
+            lines: parse(input)?,
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, SolveError> {
+        Err(SolveError(format!(
+            "not solved yet, parsed {} lines",
+            self.lines.len()
+        )))
+    }
+
+    fn part2(&self) -> Result<Answer, SolveError> {
+        Err(SolveError(format!(
+            "not solved yet, parsed {} lines",
+            self.lines.len()
+        )))
+    }
+}
+
+examples! {
+    Puzzle,
+    example: "\
+" => (None, None),
+}
+"#;
+
+fn write_new(path: &Path, contents: &str) -> Result<(), Error> {
+    if path.exists() {
+        return Err(Error::InvalidFile {
+            path: path.to_path_buf(),
+            line: None,
+            message: "already exists".to_string(),
+        });
+    }
+    fs::write(path, contents).map_err(|why| Error::io(path, why))
+}
+
+// Generates the module for a day under the crate root, registers its solver and creates an
+// empty input file for it
+pub fn run(day: u32, root: &Path, inputs: &Path) -> Result<(), Error> {
+    let entry = days::day(day).ok_or(Error::InvalidDay(day))?;
+
+    let registry = root.join("src/days/mod.rs");
+    let contents = fs::read_to_string(&registry).map_err(|why| Error::io(&registry, why))?;
+    let unsolved = format!("    {day}: \"{}\",\n", entry.title);
+    if !contents.contains(&unsolved) {
+        return Err(Error::InvalidFile {
+            path: registry,
+            line: None,
+            message: format!("no unsolved entry for day {day}"),
+        });
+    }
+
+    let module = root.join(format!("src/days/day{day:02}.rs"));
+    write_new(&module, &TEMPLATE.replace("{title}", entry.title))?;
+    println!("Created {}", module.display());
+
+    let solved = format!("    {day}: \"{}\" => day{day:02}::Puzzle,\n", entry.title);
+    fs::write(&registry, contents.replace(&unsolved, &solved))
+        .map_err(|why| Error::io(&registry, why))?;
+    println!("Registered day {day} in {}", registry.display());
+
+    let input = inputs.join(format!("day{day:02}.txt"));
+    if !input.exists() {
+        fs::create_dir_all(inputs).map_err(|why| Error::io(inputs, why))?;
+        write_new(&input, "")?;
+        println!("Created {}", input.display());
+    }
+
+    Ok(())
+}
@@ -1,26 +1,65 @@
 use std::fmt;
-use std::num::TryFromIntError;
+use std::io::{self, IsTerminal, Write};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::OnceLock;
 
+use crate::error::ParseError;
+
+pub mod parser;
+
+// Splits each line of the input into values, describing them as the expected form in errors
 pub fn parse<T: FromStr>(
     input: &str,
     deliminator: &str,
-) -> Result<Vec<Vec<T>>, <T as FromStr>::Err> {
+    expected: &str,
+) -> Result<Vec<Vec<T>>, ParseError>
+where
+    <T as FromStr>::Err: fmt::Display,
+{
     input
         .lines()
-        .map(|line| {
+        .enumerate()
+        .map(|(y, line)| {
             line.split(deliminator)
                 .filter(|x| x != &"")
-                .map(|x| str::parse::<T>(x))
+                .map(|x| parse_token(line, x, expected).map_err(|why| why.offset(y)))
                 .collect::<Result<Vec<_>, _>>()
         })
         .collect::<Result<Vec<_>, _>>()
 }
 
-#[derive(Clone, Copy, Debug)]
-pub struct Grid<T, const N: usize>([[T; N]; N]);
+// Parses a token sliced from the line, reporting its column and what was expected if it's invalid.
+// The error is on line 1, callers offset it to the line's place in the input
+pub fn parse_token<T: FromStr>(line: &str, token: &str, expected: &str) -> Result<T, ParseError>
+where
+    <T as FromStr>::Err: fmt::Display,
+{
+    str::parse::<T>(token).map_err(|why| {
+        ParseError::new(1, column(line, token), why.to_string())
+            .token(token)
+            .expected(expected)
+    })
+}
+
+// Column in characters, counting from 1, of a slice taken from the line
+pub fn column(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let offset = (token.as_ptr() as usize)
+        .saturating_sub(start)
+        .min(line.len());
+    line[..offset].chars().count() + 1
+}
+
+// Rectangular grid stored row by row, sized by the input it was parsed from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct Coord<const N: usize> {
+pub struct Coord {
     x: usize,
     y: usize,
 }
@@ -36,75 +75,198 @@ pub enum Direction {
     UpLeft,
 }
 
-impl<T: FromStr, const N: usize> Grid<T, N> {
-    pub fn new(input: &str, deliminator: &str) -> Option<Self> {
-        // Parse items to vec of vec
-        let vec = parse::<T>(input.trim(), deliminator).ok()?;
-        // Try to convert into fixed len array
-        let arr = vec
-            .into_iter()
-            .map(|x| x.try_into())
-            .collect::<Result<Vec<[T; N]>, _>>()
-            .ok()?
-            .try_into()
-            .ok()?;
-
-        Some(Grid(arr))
+impl<T: FromStr> Grid<T>
+where
+    <T as FromStr>::Err: fmt::Display,
+{
+    pub fn new(input: &str, deliminator: &str, expected: &str) -> Result<Self, ParseError> {
+        let rows = parse::<T>(input.trim_end(), deliminator, expected)?;
+        Self::from_rows(rows)
     }
 }
 
-impl<T: FromStr + Copy, const N: usize> Grid<T, N> {
-    pub fn fill(val: T) -> Self {
-        Grid([[val; N]; N])
+impl<T> Grid<T> {
+    // Grid of the rows parsed from each line of the input. Leading blank lines are skipped but
+    // still count towards the line numbers of errors
+    fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, ParseError> {
+        let skipped = rows.iter().take_while(|x| x.is_empty()).count();
+        // Every row must be as wide as the first
+        let width = rows.get(skipped).map_or(0, |x| x.len());
+        let height = rows.len() - skipped;
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate().skip(skipped) {
+            if row.len() != width {
+                return Err(ParseError::new(
+                    y + 1,
+                    row.len().min(width) + 1,
+                    format!("row has {} cells, unlike the first row", row.len()),
+                )
+                .expected(format!("{width} cells")));
+            }
+            cells.extend(row);
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
     }
 }
 
-impl<T, const N: usize> Grid<T, N> {
-    pub fn get(&self, coord: &Coord<N>) -> &T {
-        &self.0[coord.y][coord.x]
-    }
+// Grid cell read from a single character of the input
+pub trait FromChar: Sized {
+    // Characters the cell can be read from, for errors
+    const EXPECTED: &'static str;
+
+    fn from_char(c: char) -> Option<Self>;
+}
+
+impl FromChar for char {
+    const EXPECTED: &'static str = "any character";
 
-    pub fn set(&mut self, val: T, coord: Coord<N>) {
-        self.0[coord.y][coord.x] = val;
+    fn from_char(c: char) -> Option<Self> {
+        Some(c)
     }
+}
 
-    pub fn iter(&self) -> impl Iterator<Item = &T> {
-        self.0.iter().flat_map(|x| x.iter())
+// Marker character found while reading a grid, with its coord in the grid and where it was in
+// the input, lines and columns counting from 1
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Marker {
+    pub mark: char,
+    pub coord: Coord,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl<T: FromChar> Grid<T> {
+    // Grid with a cell for each character of the input
+    pub fn from_chars(input: &str) -> Result<Self, ParseError> {
+        Ok(Self::from_chars_marked(input, &[])?.0)
     }
 
-    pub fn enumerate(&self) -> Vec<(Coord<N>, &T)> {
-        self.0
-            .iter()
+    // Grid with a cell for each character of the input, along with each marker character found
+    // while parsing, in reading order
+    pub fn from_chars_marked(
+        input: &str,
+        markers: &[char],
+    ) -> Result<(Self, Vec<Marker>), ParseError> {
+        let mut found = Vec::new();
+        let rows = input
+            .trim_end()
+            .lines()
             .enumerate()
-            .flat_map(|(y, line)| {
-                line.iter()
+            .map(|(y, line)| {
+                line.chars()
                     .enumerate()
-                    .map(|(x, val)| (Coord::<N>::new(x, y).unwrap(), val))
-                    .collect::<Vec<_>>()
+                    .map(|(x, c)| {
+                        if markers.contains(&c) {
+                            found.push((c, x, y));
+                        }
+                        T::from_char(c).ok_or_else(|| {
+                            ParseError::new(y + 1, x + 1, "unexpected character")
+                                .token(c)
+                                .expected(T::EXPECTED)
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
             })
-            .collect::<Vec<_>>()
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Markers sit on the grid's rows, which start after any leading blank lines
+        let skipped = rows.iter().take_while(|x| x.is_empty()).count();
+        let grid = Self::from_rows(rows)?;
+        let found = found
+            .into_iter()
+            .map(|(mark, x, y)| Marker {
+                mark,
+                coord: Coord { x, y: y - skipped },
+                line: y + 1,
+                column: x + 1,
+            })
+            .collect();
+        Ok((grid, found))
     }
 }
 
-impl<T: Eq, const N: usize> Grid<T, N> {
-    pub fn find(&self, target: T) -> Vec<Coord<N>> {
-        self.enumerate()
-            .iter()
-            .filter(|(_, val)| **val == target)
-            .map(|(coord, _)| *coord)
-            .collect()
+impl<T: Clone> Grid<T> {
+    pub fn fill(width: usize, height: usize, val: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![val; width * height],
+        }
     }
 }
 
-impl<const N: usize> Coord<N> {
-    pub fn new(x: usize, y: usize) -> Option<Self> {
-        if x < N && y < N {
+impl<T> Grid<T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // Coord at the given position, if it lies within the grid
+    pub fn coord(&self, x: i64, y: i64) -> Option<Coord> {
+        let x: usize = x.try_into().ok()?;
+        let y: usize = y.try_into().ok()?;
+        if x < self.width && y < self.height {
             Some(Coord { x, y })
         } else {
             None
         }
     }
 
+    // Neighbouring coord in the given direction, if it lies within the grid
+    pub fn next(&self, coord: Coord, dir: Direction) -> Option<Coord> {
+        let (dx, dy) = dir.offset();
+        self.coord(coord.x as i64 + dx, coord.y as i64 + dy)
+    }
+
+    pub fn get(&self, coord: &Coord) -> &T {
+        &self.cells[coord.y * self.width + coord.x]
+    }
+
+    pub fn set(&mut self, val: T, coord: Coord) {
+        self.cells[coord.y * self.width + coord.x] = val;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Coord { x, y }))
+    }
+
+    pub fn enumerate(&self) -> Vec<(Coord, &T)> {
+        self.coords().zip(self.cells.iter()).collect()
+    }
+
+    pub fn map<U>(&self, func: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(func).collect(),
+        }
+    }
+}
+
+impl<T: Eq> Grid<T> {
+    pub fn find(&self, target: T) -> Vec<Coord> {
+        self.enumerate()
+            .iter()
+            .filter(|(_, val)| **val == target)
+            .map(|(coord, _)| *coord)
+            .collect()
+    }
+}
+
+impl Coord {
     pub fn to_tuple(&self) -> (usize, usize) {
         (self.x, self.y)
     }
@@ -116,51 +278,30 @@ impl<const N: usize> Coord<N> {
     pub fn y(&self) -> usize {
         self.y
     }
-
-    pub fn next(&self, dir: Direction) -> Option<Coord<N>> {
-        let (x, y) = (self.x as i64, self.y as i64);
-        let (x, y) = match dir {
-            Direction::Up => (x, y - 1),
-            Direction::UpRight => (x + 1, y - 1),
-            Direction::Right => (x + 1, y),
-            Direction::DownRight => (x + 1, y + 1),
-            Direction::Down => (x, y + 1),
-            Direction::DownLeft => (x - 1, y + 1),
-            Direction::Left => (x - 1, y),
-            Direction::UpLeft => (x - 1, y - 1),
-        };
-        (x, y).try_into().ok()
-    }
 }
 
-impl<const N: usize> std::ops::Add for Coord<N> {
-    type Output = (i64, i64);
-    fn add(self, rhs: Self) -> Self::Output {
-        (self.x as i64 + rhs.x as i64, self.y as i64 + rhs.y as i64)
-    }
-}
-
-impl<const N: usize> std::ops::Sub for Coord<N> {
+impl std::ops::Sub for Coord {
     type Output = (i64, i64);
     fn sub(self, rhs: Self) -> Self::Output {
         (self.x as i64 - rhs.x as i64, self.y as i64 - rhs.y as i64)
     }
 }
 
-impl<const N: usize> std::convert::TryFrom<(i64, i64)> for Coord<N> {
-    type Error = TryFromIntError;
-    fn try_from((x, y): (i64, i64)) -> Result<Self, Self::Error> {
-        let x: usize = x.try_into()?;
-        let y: usize = y.try_into()?;
-
-        match Coord::new(x, y) {
-            Some(coord) => Ok(coord),
-            None => Err(<u8 as TryFrom<u16>>::try_from(300).unwrap_err()),
-        }
+impl Direction {
+    pub fn all() -> impl Iterator<Item = Direction> {
+        vec![
+            Direction::Up,
+            Direction::UpRight,
+            Direction::Right,
+            Direction::DownRight,
+            Direction::Down,
+            Direction::DownLeft,
+            Direction::Left,
+            Direction::UpLeft,
+        ]
+        .into_iter()
     }
-}
 
-impl Direction {
     pub fn orthogonal() -> impl Iterator<Item = Direction> {
         vec![
             Direction::Up,
@@ -170,21 +311,35 @@ impl Direction {
         ]
         .into_iter()
     }
+
+    // Change in x and y from one step in this direction, with y increasing downwards
+    pub fn offset(&self) -> (i64, i64) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::UpRight => (1, -1),
+            Direction::Right => (1, 0),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (0, 1),
+            Direction::DownLeft => (-1, 1),
+            Direction::Left => (-1, 0),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
 }
 
 // Display functions
-impl<T: fmt::Display, const N: usize> fmt::Display for Grid<T, N> {
+impl<T: fmt::Display> fmt::Display for Grid<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let res = self.0.iter().fold(String::new(), |curr, line| {
-            let line = &line
-                .iter()
-                .fold(String::new(), |curr, elem| format!("{curr}{elem}"));
-            curr + line + "\n"
-        });
-        write!(f, "{res}")
+        for line in self.cells.chunks(self.width.max(1)) {
+            for elem in line {
+                write!(f, "{elem}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
     }
 }
-impl<const N: usize> fmt::Display for Coord<N> {
+impl fmt::Display for Coord {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "({},{})", self.x, self.y)
     }
@@ -202,6 +357,12 @@ impl<K: std::cmp::Eq + std::hash::Hash, V> Cache<K, V> {
     }
 }
 
+impl<K: std::cmp::Eq + std::hash::Hash, V> Default for Cache<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<K: std::cmp::Eq + std::hash::Hash + Clone, V: Clone> Cache<K, V> {
     pub fn get_or_set<F>(&mut self, key: K, func: F) -> V
     where
@@ -262,3 +423,290 @@ impl<T: std::ops::Div<Output = T>> std::ops::Div for Vector<T> {
         }
     }
 }
+
+// Progress reporting
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+// Stops any progress being drawn, for when only the answers should be output
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+// Percentage through a long running loop, redrawn in place on stderr whenever it changes. Nothing
+// is drawn when quiet or when stderr isn't a terminal, so piped output stays clean
+pub struct Progress {
+    label: &'static str,
+    total: usize,
+    percent: Option<usize>,
+    enabled: bool,
+}
+
+impl Progress {
+    pub fn new(label: &'static str, total: usize) -> Self {
+        Self::sized_by(label, || total)
+    }
+
+    // Progress whose total is costly to work out, only computed when the progress is drawn
+    pub fn sized_by(label: &'static str, total: impl FnOnce() -> usize) -> Self {
+        let enabled = !QUIET.load(Ordering::Relaxed) && io::stderr().is_terminal();
+        Progress {
+            label,
+            total: if enabled { total() } else { 0 },
+            percent: None,
+            enabled,
+        }
+    }
+
+    pub fn update(&mut self, done: usize) {
+        self.update_with(done, "");
+    }
+
+    // Updates the progress, showing some detail of the current item alongside it
+    pub fn update_with(&mut self, done: usize, detail: impl fmt::Display) {
+        if !self.enabled {
+            return;
+        }
+        let percent = done * 100 / self.total.max(1);
+        if self.percent != Some(percent) {
+            self.percent = Some(percent);
+            let mut stderr = io::stderr();
+            let _ = write!(stderr, "\r\x1b[K{} {percent}% {detail}", self.label);
+            let _ = stderr.flush();
+        }
+    }
+}
+
+impl Drop for Progress {
+    // Clears the progress line once the loop is done
+    fn drop(&mut self) {
+        if self.enabled && self.percent.is_some() {
+            let _ = write!(io::stderr(), "\r\x1b[K");
+        }
+    }
+}
+
+// Tracing
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Debug,
+    Trace,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Off => "off",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Level::Off),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!("unknown level '{s}', expected off, debug or trace")),
+        }
+    }
+}
+
+// Which messages to show, a default level plus overrides for single days, written like
+// "debug,day10=trace,day06=off"
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogFilter {
+    pub level: Level,
+    days: Vec<(u32, Level)>,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        LogFilter {
+            level: Level::Off,
+            days: Vec::new(),
+        }
+    }
+}
+
+impl LogFilter {
+    fn level(&self, day: Option<u32>) -> Level {
+        self.days
+            .iter()
+            .find(|(x, _)| Some(*x) == day)
+            .map_or(self.level, |(_, level)| *level)
+    }
+
+    fn max(&self) -> Level {
+        self.days
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.level, Level::max)
+    }
+}
+
+impl FromStr for LogFilter {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = LogFilter::default();
+        for directive in s.split(',').map(str::trim).filter(|x| !x.is_empty()) {
+            match directive.split_once('=') {
+                Some((day, level)) => {
+                    let number = day
+                        .strip_prefix("day")
+                        .and_then(|x| str::parse(x).ok())
+                        .ok_or_else(|| format!("expected a day like day06, found '{day}'"))?;
+                    filter.days.push((number, level.parse()?));
+                }
+                None => filter.level = directive.parse()?,
+            }
+        }
+        Ok(filter)
+    }
+}
+
+static LOG_FILTER: OnceLock<LogFilter> = OnceLock::new();
+static LOG_MAX: AtomicU8 = AtomicU8::new(Level::Off as u8);
+
+// Sets which messages are shown, only the first call has any effect
+pub fn set_log_filter(filter: LogFilter) {
+    LOG_MAX.store(filter.max() as u8, Ordering::Relaxed);
+    let _ = LOG_FILTER.set(filter);
+}
+
+// Day a message came from, taken from the dayNN module it was logged in
+fn module_day(module: &str) -> Option<u32> {
+    let name = module.rsplit("::").find(|x| x.starts_with("day"))?;
+    str::parse(&name[3..]).ok()
+}
+
+// Writes the message to stderr if the filter allows its level for the day it came from, use
+// through the debug! and trace! macros
+pub fn log(level: Level, module: &str, args: fmt::Arguments) {
+    if level as u8 > LOG_MAX.load(Ordering::Relaxed) {
+        return;
+    }
+    let Some(filter) = LOG_FILTER.get() else {
+        return;
+    };
+    let day = module_day(module);
+    if level <= filter.level(day) {
+        match day {
+            Some(day) => eprintln!("[{level} day{day:02}] {args}"),
+            None => eprintln!("[{level}] {args}"),
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::shared::log($crate::shared::Level::Debug, module_path!(), format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::shared::log($crate::shared::Level::Trace, module_path!(), format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rectangular_grid() {
+        let grid = Grid::<u32>::new("1 2 3 4\n5 6 7 8\n", " ", "a digit").unwrap();
+        assert_eq!((grid.width(), grid.height()), (4, 2));
+        assert_eq!(grid.to_string(), "1234\n5678\n");
+
+        // Bounds follow the width along x and the height along y
+        assert!(grid.coord(3, 1).is_some());
+        assert_eq!(grid.coord(1, 3), None);
+        assert_eq!(grid.coord(4, 0), None);
+        assert_eq!(grid.coord(-1, 0), None);
+
+        let corner = grid.coord(3, 1).unwrap();
+        assert_eq!(*grid.get(&corner), 8);
+        assert_eq!(grid.next(corner, Direction::Right), None);
+        assert_eq!(grid.next(corner, Direction::Down), None);
+        let up = grid.next(corner, Direction::UpLeft).unwrap();
+        assert_eq!((up.to_tuple(), *grid.get(&up)), ((2, 0), 3));
+
+        let coords = grid.coords().map(|x| x.to_tuple()).collect::<Vec<_>>();
+        assert_eq!(coords.len(), 8);
+        assert_eq!(&coords[3..5], [(3, 0), (0, 1)]);
+        assert_eq!(grid.find(5), vec![grid.coord(0, 1).unwrap()]);
+
+        let mut filled = Grid::fill(3, 2, '.');
+        filled.set('#', filled.coord(2, 1).unwrap());
+        assert_eq!((filled.width(), filled.height()), (3, 2));
+        assert_eq!(filled.to_string(), "...\n..#\n");
+        assert_eq!(filled.coord(0, 2), None);
+    }
+
+    #[test]
+    fn grid_from_chars() {
+        let grid = Grid::<char>::from_chars("\n\nab\ncd\n\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid.to_string(), "ab\ncd\n");
+
+        // Errors keep the line numbers of the input after leading blank lines
+        let error = Grid::<Marked>::from_chars("\n\n..\n.x\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 2));
+        assert_eq!(error.token.as_deref(), Some("x"));
+        assert_eq!(error.expected.as_deref(), Some(Marked::EXPECTED));
+    }
+
+    #[test]
+    fn grid_from_ragged_chars() {
+        let error = Grid::<char>::from_chars("\nabc\nab\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 3));
+        assert_eq!(error.expected.as_deref(), Some("3 cells"));
+        let error = Grid::<char>::from_chars("ab\nabc").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        // A blank line inside the grid is a row without cells
+        let error = Grid::<char>::from_chars("ab\n\nab").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn grid_from_marked_chars() {
+        let (grid, markers) = Grid::<Marked>::from_chars_marked("\n\n..^\n.^.\n", &['^']).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        let at = |x: &Marker| (x.coord.to_tuple(), x.line, x.column);
+        assert_eq!(
+            markers.iter().map(at).collect::<Vec<_>>(),
+            [((2, 0), 3, 3), ((1, 1), 4, 2)]
+        );
+        assert_eq!(*grid.get(&markers[1].coord), Marked::Marker);
+
+        let (_, markers) = Grid::<char>::from_chars_marked("a.b\nb.a", &['a', 'b']).unwrap();
+        let marks = markers.iter().map(|x| x.mark).collect::<String>();
+        assert_eq!(marks, "abba");
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Marked {
+        Empty,
+        Marker,
+    }
+
+    impl FromChar for Marked {
+        const EXPECTED: &'static str = ". or ^";
+
+        fn from_char(c: char) -> Option<Self> {
+            match c {
+                '.' => Some(Marked::Empty),
+                '^' => Some(Marked::Marker),
+                _ => None,
+            }
+        }
+    }
+}
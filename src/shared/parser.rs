@@ -0,0 +1,340 @@
+// Parser combinators for puzzle inputs. A parser takes the input left to parse and returns what
+// it read along with the input after it, errors point at the line and column they were found at
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+// Input left to parse, with the line and column it starts at
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Input<'a> {
+    rest: &'a str,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Input {
+            rest: input,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    // Input after the first len bytes, columns count characters
+    fn advance(self, len: usize) -> Self {
+        let (taken, rest) = self.rest.split_at(len);
+        let (line, column) = match taken.rfind('\n') {
+            Some(i) => (
+                self.line + taken.matches('\n').count(),
+                taken[i + 1..].chars().count() + 1,
+            ),
+            None => (self.line, self.column + taken.chars().count()),
+        };
+        Input { rest, line, column }
+    }
+
+    // Error at the start of the input, noting the word there as the offending token, or the
+    // single character if it's whitespace. Nothing is noted at the end of a line
+    pub fn error(&self, message: impl Into<String>, expected: impl Into<String>) -> ParseError {
+        let line = self.rest.lines().next().unwrap_or_default();
+        let first = line.chars().next().map_or(0, char::len_utf8);
+        let len = line
+            .find(char::is_whitespace)
+            .unwrap_or(line.len())
+            .max(first);
+        let error = ParseError::new(self.line, self.column, message).expected(expected);
+        match len {
+            0 => error,
+            len => error.token(&line[..len]),
+        }
+    }
+}
+
+pub type ParseResult<'a, T> = Result<(T, Input<'a>), ParseError>;
+
+pub trait Parser<'a, T>: Fn(Input<'a>) -> ParseResult<'a, T> {}
+
+impl<'a, T, F: Fn(Input<'a>) -> ParseResult<'a, T>> Parser<'a, T> for F {}
+
+// Runs the parser over the whole input, leading blank lines are skipped and only trailing
+// whitespace may be left over
+pub fn parse_all<'a, T>(parser: impl Parser<'a, T>, input: &'a str) -> Result<T, ParseError> {
+    let input = input.trim_end();
+    let blank = &input[..input.len() - input.trim_start().len()];
+    let start = Input::new(input).advance(blank.rfind('\n').map_or(0, |i| i + 1));
+    let (res, rest) = parser(start)?;
+    if !rest.rest.is_empty() {
+        return Err(rest.error("unexpected input", "end of input"));
+    }
+    Ok(res)
+}
+
+// Integer with an optional sign, read up to the next character that can't be part of a word
+pub fn int<'a, T: FromStr>() -> impl Parser<'a, T>
+where
+    <T as FromStr>::Err: fmt::Display,
+{
+    move |input: Input<'a>| {
+        let expected = "an integer";
+        let sign = input.rest.starts_with(['-', '+']) as usize;
+        let len = input.rest[sign..]
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .map_or(input.rest.len(), |x| x + sign);
+        if len == sign {
+            return Err(input.error("missing number", expected));
+        }
+
+        let token = &input.rest[..len];
+        let val = str::parse::<T>(token).map_err(|why| {
+            ParseError::new(input.line, input.column, why.to_string())
+                .token(token)
+                .expected(expected)
+        })?;
+        Ok((val, input.advance(len)))
+    }
+}
+
+pub fn literal<'a>(lit: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| {
+        if input.rest.starts_with(lit) {
+            Ok((&input.rest[..lit.len()], input.advance(lit.len())))
+        } else {
+            Err(input.error(format!("missing {lit:?}"), format!("{lit:?}")))
+        }
+    }
+}
+
+// Line break, \n or \r\n, that doesn't start a blank line, so a run of lines stops at the end of
+// its section
+pub fn newline<'a>() -> impl Parser<'a, ()> {
+    move |input: Input<'a>| {
+        let rest = input.rest.strip_prefix('\r').unwrap_or(input.rest);
+        match rest.strip_prefix('\n') {
+            Some(rest) if !rest.starts_with(['\n', '\r']) => {
+                Ok(((), input.advance(input.rest.len() - rest.len())))
+            }
+            _ => Err(input.error("missing line break", "a line break")),
+        }
+    }
+}
+
+// Blank lines between sections, any number of them
+pub fn blank_line<'a>() -> impl Parser<'a, ()> {
+    move |input: Input<'a>| {
+        let len = input.rest.len() - input.rest.trim_start_matches(['\n', '\r']).len();
+        if input.rest[..len].matches('\n').count() >= 2 {
+            Ok(((), input.advance(len)))
+        } else {
+            Err(input.error("missing blank line", "a blank line"))
+        }
+    }
+}
+
+// One or more items with a separator between each. Stops when no separator follows an item, an
+// item that fails after a separator is an error
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    sep: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: Input<'a>| {
+        let (first, mut input) = item(input)?;
+        let mut res = vec![first];
+        while let Ok((_, next)) = sep(input) {
+            let (val, next) = item(next)?;
+            res.push(val);
+            input = next;
+        }
+        Ok((res, input))
+    }
+}
+
+// Item on each line of a section
+pub fn lines<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    separated(item, newline())
+}
+
+// Sections split by blank lines, each read by the same parser
+pub fn sections<'a, T>(section: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    separated(section, blank_line())
+}
+
+// Key and value either side of a separator, as in "190: 10 19"
+pub fn key_value<'a, K, V>(
+    key: impl Parser<'a, K>,
+    sep: &'static str,
+    value: impl Parser<'a, V>,
+) -> impl Parser<'a, (K, V)> {
+    pair(terminated(key, literal(sep)), value)
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input: Input<'a>| {
+        let (a, input) = first(input)?;
+        let (b, input) = second(input)?;
+        Ok(((a, b), input))
+    }
+}
+
+// Second parser's result, after the first
+pub fn preceded<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, B> {
+    move |input: Input<'a>| {
+        let (_, input) = first(input)?;
+        second(input)
+    }
+}
+
+// First parser's result, before the second
+pub fn terminated<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, A> {
+    move |input: Input<'a>| {
+        let (a, input) = first(input)?;
+        let (_, input) = second(input)?;
+        Ok((a, input))
+    }
+}
+
+pub fn map<'a, T, U>(parser: impl Parser<'a, T>, func: impl Fn(T) -> U) -> impl Parser<'a, U> {
+    move |input: Input<'a>| {
+        let (val, input) = parser(input)?;
+        Ok((func(val), input))
+    }
+}
+
+// Parser whose errors describe what was expected by the given name, such as "a page number"
+pub fn named<'a, T>(parser: impl Parser<'a, T>, name: &'static str) -> impl Parser<'a, T> {
+    move |input: Input<'a>| parser(input).map_err(|why| why.expected(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Line and column an error was found at
+    fn at(error: ParseError) -> (usize, usize) {
+        (error.line, error.column)
+    }
+
+    #[test]
+    fn ints() {
+        let (val, rest) = int::<i64>()(Input::new("-12,3")).unwrap();
+        assert_eq!((val, rest.rest(), rest.column), (-12, ",3", 4));
+        assert_eq!(int::<i32>()(Input::new("+7")).unwrap().0, 7);
+        assert_eq!(int::<u32>()(Input::new("42")).unwrap().0, 42);
+
+        let error = int::<u32>()(Input::new("-3")).unwrap_err();
+        assert_eq!(error.token.as_deref(), Some("-3"));
+        let error = int::<u32>()(Input::new("4x2 1")).unwrap_err();
+        assert_eq!(error.token.as_deref(), Some("4x2"));
+        assert_eq!(error.expected.as_deref(), Some("an integer"));
+        let error = int::<u32>()(Input::new("-")).unwrap_err();
+        assert_eq!(error.message, "missing number");
+        assert!(int::<u32>()(Input::new(" 1")).is_err());
+    }
+
+    #[test]
+    fn separated_lists() {
+        let list = separated(int::<u32>(), literal(","));
+        let (val, rest) = list(Input::new("1,2,3 x")).unwrap();
+        assert_eq!((val, rest.rest()), (vec![1, 2, 3], " x"));
+
+        // A separator commits to another item
+        let error = list(Input::new("1,2,")).unwrap_err();
+        assert_eq!(at(error), (1, 5));
+        let error = parse_all(separated(int::<u32>(), literal(",")), "1,2,\n").unwrap_err();
+        assert_eq!(error.message, "missing number");
+    }
+
+    #[test]
+    fn lines_stop_at_blank_line() {
+        let input = Input::new("1\n2\n\n3");
+        let (val, rest) = lines(int::<u32>())(input).unwrap();
+        assert_eq!((val, rest.rest()), (vec![1, 2], "\n\n3"));
+        assert_eq!(at(newline()(rest).unwrap_err()), (2, 2));
+
+        let (_, rest) = blank_line()(rest).unwrap();
+        assert_eq!((rest.line, rest.column), (4, 1));
+        assert!(blank_line()(Input::new("\n3")).is_err());
+    }
+
+    #[test]
+    fn crlf_line_breaks() {
+        let grammar = sections(lines(separated(int::<u32>(), literal(" "))));
+        let val = parse_all(grammar, "1 2\r\n3\r\n\r\n4\r\n").unwrap();
+        assert_eq!(val, vec![vec![vec![1, 2], vec![3]], vec![vec![4]]]);
+
+        let (_, rest) = newline()(Input::new("\r\nab")).unwrap();
+        assert_eq!((rest.line, rest.column, rest.rest()), (2, 1, "ab"));
+        assert!(newline()(Input::new("\r\n\r\n1")).is_err());
+        assert!(newline()(Input::new("\r1")).is_err());
+
+        let error = parse_all(lines(int::<u32>()), "1\r\nx\r\n").unwrap_err();
+        assert_eq!(at(error), (2, 1));
+    }
+
+    #[test]
+    fn sections_of_lines() {
+        let grammar = sections(lines(int::<u32>()));
+        let val = parse_all(grammar, "1\n2\n\n\n3\n\n4\n").unwrap();
+        assert_eq!(val, vec![vec![1, 2], vec![3], vec![4]]);
+
+        let error = parse_all(sections(lines(int::<u32>())), "1\n\n2\nx").unwrap_err();
+        assert_eq!(at(error), (4, 1));
+    }
+
+    #[test]
+    fn key_values() {
+        let grammar = key_value(int::<u64>(), ": ", separated(int::<u32>(), literal(" ")));
+        let val = parse_all(grammar, "190: 10 19").unwrap();
+        assert_eq!(val, (190, vec![10, 19]));
+
+        let grammar = key_value(int::<u64>(), ": ", int::<u32>());
+        let error = parse_all(grammar, "190 10").unwrap_err();
+        assert_eq!(
+            (at(error.clone()), error.token.as_deref()),
+            ((1, 4), Some(" "))
+        );
+    }
+
+    #[test]
+    fn positions_across_lines() {
+        let input = Input::new("ab\ncdé\nfg");
+        let next = input.advance(3);
+        assert_eq!((next.line, next.column), (2, 1));
+        let next = input.advance("ab\ncdé".len());
+        assert_eq!((next.line, next.column), (2, 4));
+        let next = next.advance(2);
+        assert_eq!((next.line, next.column, next.rest()), (3, 2, "g"));
+
+        let error = parse_all(preceded(literal("é"), int::<u32>()), "é1x").unwrap_err();
+        assert_eq!(
+            (at(error.clone()), error.token.as_deref()),
+            ((1, 2), Some("1x"))
+        );
+    }
+
+    #[test]
+    fn leading_blank_lines() {
+        let val = parse_all(lines(int::<u32>()), "\n \n1\n2\n\n").unwrap();
+        assert_eq!(val, vec![1, 2]);
+
+        let error = parse_all(lines(int::<u32>()), "\n\n1\nx").unwrap_err();
+        assert_eq!(at(error), (4, 1));
+        // Only whole blank lines are skipped, not the indent of the first line
+        let error = parse_all(int::<u32>(), "\n  x").unwrap_err();
+        assert_eq!(at(error), (2, 1));
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use advent_of_code_2024::{days, Answer, Error};
+
+use crate::output::print_table;
+use crate::{collect_records, Args};
+
+// Known answers keyed by day and part, stored one per line as "day part answer". Lines starting
+// with # are comments, the file is kept as written and recorded answers are appended to it
+pub struct AnswerFile {
+    answers: BTreeMap<(u32, u8), String>,
+    contents: String,
+    added: Vec<((u32, u8), String)>,
+}
+
+impl AnswerFile {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        fn parse_line(line: &str) -> Option<((u32, u8), String)> {
+            let mut split = line.splitn(3, ' ');
+            let day = str::parse(split.next()?).ok()?;
+            let part = str::parse(split.next()?).ok()?;
+            let answer = split.next()?.trim().to_string();
+            Some(((day, part), answer))
+        }
+
+        if !path.exists() {
+            return Ok(AnswerFile {
+                answers: BTreeMap::new(),
+                contents: String::new(),
+                added: Vec::new(),
+            });
+        }
+        let contents = fs::read_to_string(path).map_err(|why| Error::io(path, why))?;
+        let answers = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(y, line)| {
+                parse_line(line).ok_or_else(|| Error::InvalidFile {
+                    path: path.to_path_buf(),
+                    line: Some(y + 1),
+                    message: format!("invalid answer line '{line}'"),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(AnswerFile {
+            answers,
+            contents,
+            added: Vec::new(),
+        })
+    }
+
+    // Writes the file as it was loaded with the answers added since appended to it
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let mut contents = self.contents.clone();
+        if !contents.is_empty() && !contents.ends_with('\n') {
+            contents.push('\n');
+        }
+        for ((day, part), answer) in &self.added {
+            contents.push_str(&format!("{day} {part} {answer}\n"));
+        }
+        fs::write(path, contents).map_err(|why| Error::io(path, why))
+    }
+
+    pub fn get(&self, day: u32, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(|x| &x[..])
+    }
+
+    // Records an answer that isn't known yet, known answers are left as they are
+    pub fn add(&mut self, day: u32, part: u8, answer: &Answer) {
+        if self.answers.contains_key(&(day, part)) {
+            return;
+        }
+        self.answers.insert((day, part), answer.to_string());
+        self.added.push(((day, part), answer.to_string()));
+    }
+
+    pub fn check(&self, day: u32, part: u8, answer: &Answer) -> Status {
+        match self.get(day, part) {
+            Some(expected) if expected == answer.to_string() => Status::Pass,
+            Some(_) => Status::Fail,
+            None => Status::Missing,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Status::Pass => "PASS",
+            Status::Fail => "FAIL",
+            Status::Missing => "MISSING",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// Runs each selected day against its worked examples, checking the answers given in the puzzle
+// text, returns whether they all matched
+pub fn run_examples(selected: RangeInclusive<u32>, args: &Args) -> bool {
+    let mut rows = Vec::new();
+    let mut passed = true;
+    for day in selected {
+        for example in days::examples(day) {
+            let results = match example.run(day, args.part) {
+                Ok(results) => results,
+                Err(why) => {
+                    passed = false;
+                    rows.push(vec![
+                        day.to_string(),
+                        example.name.to_string(),
+                        "-".to_string(),
+                        why.to_string(),
+                        "-".to_string(),
+                        Status::Fail.to_string(),
+                    ]);
+                    continue;
+                }
+            };
+            for (part, answer, expected) in results {
+                let status = if answer.to_string() == expected {
+                    Status::Pass
+                } else {
+                    passed = false;
+                    Status::Fail
+                };
+                rows.push(vec![
+                    day.to_string(),
+                    example.name.to_string(),
+                    part.to_string(),
+                    answer.to_string(),
+                    expected.to_string(),
+                    status.to_string(),
+                ]);
+            }
+        }
+    }
+
+    print_table(
+        &["Day", "Example", "Part", "Answer", "Expected", "Status"],
+        &rows,
+    );
+
+    passed
+}
+
+// Runs each selected day, checking its answers against the answers file and optionally
+// recording any that are missing, returns whether every known answer matched. Parse and solver
+// errors count as failures
+pub fn run(
+    selected: RangeInclusive<u32>,
+    args: &Args,
+    path: &Path,
+    record: bool,
+) -> Result<bool, Error> {
+    let mut known = AnswerFile::load(path)?;
+
+    let mut rows = Vec::new();
+    let mut passed = true;
+    let records = collect_records(selected, args);
+    for day_records in records.chunk_by(|l, r| l.day == r.day) {
+        let day = day_records[0].day;
+        match &day_records[0].error {
+            Some(Error::Unimplemented(_)) => continue,
+            Some(why @ Error::Io { .. }) => {
+                eprintln!("Skipping day {day}: {why}");
+                continue;
+            }
+            _ => {}
+        }
+
+        for x in day_records {
+            let part = x.part;
+            let Some(answer) = &x.answer else {
+                passed = false;
+                let why = x.error.as_ref().map(|x| x.to_string()).unwrap_or_default();
+                let expected = known.get(day, part).unwrap_or("-").to_string();
+                rows.push(vec![
+                    day.to_string(),
+                    part.to_string(),
+                    why,
+                    expected,
+                    Status::Fail.to_string(),
+                ]);
+                continue;
+            };
+            let status = known.check(day, part, answer);
+            rows.push(vec![
+                day.to_string(),
+                part.to_string(),
+                answer.to_string(),
+                known.get(day, part).unwrap_or("-").to_string(),
+                status.to_string(),
+            ]);
+
+            match status {
+                Status::Fail => passed = false,
+                Status::Missing if record => known.add(day, part, answer),
+                _ => {}
+            }
+        }
+    }
+
+    print_table(&["Day", "Part", "Answer", "Expected", "Status"], &rows);
+
+    if record {
+        known.save(path)?;
+    }
+
+    Ok(passed)
+}
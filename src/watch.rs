@@ -0,0 +1,80 @@
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use advent_of_code_2024::{days, Error};
+
+use crate::output::{print_records, Format};
+use crate::{collect_records, Args};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// Files the selected days read their input from
+fn watched(selected: &RangeInclusive<u32>, args: &Args) -> Vec<PathBuf> {
+    match &args.input {
+        Some(path) => vec![PathBuf::from(path)],
+        None => selected
+            .clone()
+            .filter(|day| days::is_implemented(*day))
+            .map(|day| args.inputs.join(format!("day{day:02}.txt")))
+            .collect(),
+    }
+}
+
+// Modification time of each file, None for files that don't exist yet
+fn modified(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| path.metadata().and_then(|x| x.modified()).ok())
+        .collect()
+}
+
+// Runs the selected days and prints their answers and timings, reporting any errors without
+// stopping. The text table already shows the errors
+fn rerun(selected: &RangeInclusive<u32>, args: &Args) {
+    let records = collect_records(selected.clone(), args);
+    print_records(&records, args.format);
+    if args.format == Format::Text {
+        return;
+    }
+
+    let mut errors = records
+        .iter()
+        .filter_map(|x| match &x.error {
+            Some(Error::Unimplemented(_)) | None => None,
+            Some(error) => Some(error.to_string()),
+        })
+        .collect::<Vec<_>>();
+    errors.dedup();
+    for error in errors {
+        eprintln!("error: {error}");
+    }
+}
+
+// Runs the selected days, then again each time one of their input files changes, until killed
+pub fn run(selected: RangeInclusive<u32>, args: &Args) {
+    let paths = watched(&selected, args);
+    let mut seen = modified(&paths);
+
+    loop {
+        rerun(&selected, args);
+        eprintln!("Waiting for changes to the input files");
+
+        let changed = loop {
+            thread::sleep(POLL_INTERVAL);
+            let now = modified(&paths);
+            if now != seen {
+                let changed = paths
+                    .iter()
+                    .zip(now.iter().zip(&seen))
+                    .filter(|(_, (new, old))| new != old)
+                    .map(|(path, _)| path.display().to_string())
+                    .collect::<Vec<_>>();
+                seen = now;
+                break changed;
+            }
+        };
+        eprintln!("\n{} changed, re-running", changed.join(", "));
+    }
+}